@@ -0,0 +1,332 @@
+use std::io::{self, Read};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use console::Term;
+use roblox_api::{
+    api::users,
+    client::{Client, Cookie},
+};
+
+use super::challenge;
+use crate::{
+    config::{self, Account, Config},
+    error::{self, Context, Error, Result},
+    object,
+    object::Value,
+    output,
+    secret::{self, Backend, totp},
+    web::{self, WebClient, auth::SessionCookie},
+};
+
+fn position(cfg: &Config, name: &str) -> Result<usize> {
+    cfg.position(name).ok_or(Error::Config(format!(
+        "account with username: {name} not found"
+    )))
+}
+
+pub(crate) async fn add(
+    mut cfg: Config,
+    name: &str,
+    cookie: &str,
+    expires: Option<DateTime<Utc>>,
+) -> Result<()> {
+    if cfg.position(name).is_some() {
+        return Err(Error::Config(format!(
+            "account with username: {name} already exists"
+        )));
+    }
+
+    let mut client = Client::from_cookie(Cookie::from(cookie));
+    let details = users::v1::authenticated_details(&mut client)
+        .await
+        .map_err(|error| match Error::from(error) {
+            Error::Auth(_) => Error::Auth("cookie is invalid or expired".to_string()),
+            error => error,
+        })
+        .context("failed to validate cookie")?;
+
+    for account in cfg.accounts.iter().filter(|x| x.id == Some(details.id)) {
+        eprintln!(
+            "warn: user: {} is already stored as account: {}",
+            details.name, account.name
+        );
+    }
+
+    let mut account = Account {
+        name: name.to_lowercase(),
+        id: Some(details.id),
+        username: Some(details.name.to_owned()),
+        expires,
+        ..Default::default()
+    };
+
+    secret::store(&mut account, cfg.backend(), cookie)?;
+    cfg.accounts.push(account);
+
+    config::store(&cfg)?;
+    println!("info: added account: {name} ({}) to the list", details.name);
+    Ok(())
+}
+
+pub(crate) fn list(cfg: &Config) {
+    let accounts: Vec<Value> = cfg
+        .accounts
+        .iter()
+        .enumerate()
+        .map(|(index, account)| {
+            Value::from(object!(("Account", {
+                ("Name", account.name.to_owned()),
+                ("Username", account.username.to_owned()),
+                ("Id", account.id),
+                ("Expires", account.expires.map(|x| Value::Timestamp(x.fixed_offset()))),
+                ("Default", index == 0),
+            })))
+        })
+        .collect();
+
+    output::print(&object!(("Accounts", accounts)));
+}
+
+pub(crate) fn remove(mut cfg: Config, name: &str) -> Result<()> {
+    let index = position(&cfg, name)?;
+    let account = cfg.accounts.remove(index);
+
+    config::store(&cfg)?;
+    for reference in [&account.secret, &account.totp_reference]
+        .into_iter()
+        .flatten()
+    {
+        secret::delete(reference)?;
+    }
+
+    println!("info: removed account: {} from the list", account.name);
+    Ok(())
+}
+
+/// Swaps the cookie of account `name` for `cookie`, keeping it in the backend it was in
+pub(crate) fn replace_cookie(mut cfg: Config, name: &str, cookie: &SessionCookie) -> Result<()> {
+    let index = position(&cfg, name)?;
+    let account = &mut cfg.accounts[index];
+    let previous = account.secret.clone();
+
+    secret::store(account, secret::backend(account), &cookie.value)?;
+    account.expires = cookie.expires;
+    config::store(&cfg)?;
+
+    if let Some(reference) = previous {
+        secret::delete(&reference)?;
+    }
+
+    Ok(())
+}
+
+/// Swaps the cookies which expire within `days` for new sessions and logs the old ones out, every
+/// cookie with `all`.
+///
+/// Cookies whose expiry isn't known were imported from a browser or pasted, logging them out would
+/// log the browser out as well, so they're only refreshed with `all`
+pub(crate) async fn refresh(mut cfg: Config, days: u32, all: bool) -> Result<()> {
+    let deadline = Utc::now() + TimeDelta::days(days.into());
+
+    let mut failures = Vec::new();
+    let mut previous = Vec::new();
+    let mut unknown = Vec::new();
+
+    for account in &mut cfg.accounts {
+        if !all && account.expires.is_none() {
+            unknown.push(account.name.clone());
+            continue;
+        }
+
+        if !all && account.expires.is_some_and(|expires| expires > deadline) {
+            continue;
+        }
+
+        let context = format!("failed to refresh account {}", account.name);
+        let result = async {
+            let cookie = secret::load(account)?;
+            let mut client = Client::from_cookie(Cookie::from(cookie.as_str()));
+            let ticket = challenge::retry(
+                &mut client,
+                &mut WebClient::from_cookie(&cookie),
+                Some(account),
+                async |_, web| web::auth::authentication_ticket(web).await,
+            )
+            .await?;
+            let new = web::auth::redeem_authentication_ticket(&mut WebClient::anonymous(), &ticket)
+                .await?;
+
+            let reference = account.secret.clone();
+            if let Err(error) = secret::store(account, secret::backend(account), &new.value) {
+                // The account keeps the old cookie, so nothing would ever use the new session
+                let _ = web::auth::logout(&mut WebClient::from_cookie(&new.value)).await;
+                return Err(error);
+            }
+
+            account.expires = new.expires;
+            Ok::<_, Error>((cookie, reference))
+        }
+        .await
+        .context(&context);
+
+        match result {
+            Ok((cookie, reference)) => previous.push((account.name.clone(), cookie, reference)),
+            Err(error) => failures.push(Err(error)),
+        }
+    }
+
+    if !unknown.is_empty() {
+        eprintln!(
+            "info: skipped {}, the expiry of their cookies isn't known, refresh them with --all",
+            unknown.join(", ")
+        );
+    }
+
+    if previous.is_empty() {
+        if failures.is_empty() {
+            println!("info: no cookie expires within {days} days");
+        }
+
+        return error::report_all(failures);
+    }
+
+    config::store(&cfg)?;
+
+    // Only logged out once the config points at the new ones, so a failure never loses a session
+    for (name, cookie, reference) in previous {
+        if let Err(error) = web::auth::logout(&mut WebClient::from_cookie(&cookie))
+            .await
+            .context(&format!(
+                "failed to log out the old session of account {name}"
+            ))
+        {
+            error::print(&error);
+        }
+
+        if let Some(reference) = reference
+            && let Err(error) = secret::delete(&reference)
+        {
+            error::print(&error);
+        }
+
+        println!("info: refreshed the cookie of account: {name}");
+    }
+
+    error::report_all(failures)
+}
+
+pub(crate) fn rename(mut cfg: Config, name: &str, new_name: &str) -> Result<()> {
+    let index = position(&cfg, name)?;
+
+    if cfg.position(new_name).is_some_and(|other| other != index) {
+        return Err(Error::Config(format!(
+            "account with username: {new_name} already exists"
+        )));
+    }
+
+    cfg.accounts[index].name = new_name.to_lowercase();
+
+    config::store(&cfg)?;
+    println!("info: renamed account: {name} to {new_name}");
+    Ok(())
+}
+
+pub(crate) fn set_default(mut cfg: Config, name: &str) -> Result<()> {
+    let index = position(&cfg, name)?;
+    let account = cfg.accounts.remove(index);
+    cfg.accounts.insert(0, account);
+
+    config::store(&cfg)?;
+    println!("info: account: {name} is now the default");
+    Ok(())
+}
+
+/// Moves every cookie and authenticator secret into `backend`, which new accounts are stored in
+/// from then on
+pub(crate) fn migrate(mut cfg: Config, backend: Backend) -> Result<()> {
+    let mut previous = Vec::new();
+    for account in &mut cfg.accounts {
+        if secret::backend(account) != backend {
+            let cookie = secret::load(account)?;
+            previous.push(account.secret.clone());
+            secret::store(account, backend, &cookie)?;
+        }
+
+        if secret::totp_backend(account) != backend
+            && let Some(totp) = secret::load_totp(account)?
+        {
+            previous.push(account.totp_reference.clone());
+            secret::store_totp(account, backend, &totp)?;
+        }
+    }
+
+    cfg.secret_backend = Some(backend);
+    config::store(&cfg)?;
+
+    // Only dropped once the config points at the new ones, so a failure never loses a secret
+    for reference in previous.iter().flatten() {
+        if let Err(error) = secret::delete(reference) {
+            error::print(&error);
+        }
+    }
+
+    println!(
+        "info: moved {} secrets to the {backend} backend",
+        previous.len()
+    );
+    Ok(())
+}
+
+/// Stores the authenticator secret two step verification codes are generated from, read from
+/// stdin with `from_stdin` and asked for otherwise, or removes it with `clear`
+pub(crate) fn set_totp(mut cfg: Config, name: &str, from_stdin: bool, clear: bool) -> Result<()> {
+    let index = position(&cfg, name)?;
+    let previous = cfg.accounts[index].totp_reference.clone();
+
+    let secret = match (from_stdin, clear) {
+        (_, true) => None,
+        (true, false) => {
+            let mut secret = String::new();
+            io::stdin()
+                .read_to_string(&mut secret)
+                .context("failed to read the authenticator secret from stdin")?;
+            Some(secret.trim().to_owned())
+        }
+        (false, false) => {
+            let term = Term::stderr();
+            term.write_str("Authenticator secret: ")
+                .context("failed to write to stderr")?;
+            Some(
+                term.read_secure_line()
+                    .context("failed to read the authenticator secret")?,
+            )
+        }
+    };
+
+    let backend = cfg.backend();
+    let account = &mut cfg.accounts[index];
+    match &secret {
+        Some(secret) => {
+            // Catches a mistyped secret now instead of at the first challenge
+            totp::code(secret, 0)?;
+            secret::store_totp(account, backend, secret)?;
+        }
+
+        None => {
+            account.totp_secret = None;
+            account.totp_reference = None;
+        }
+    }
+
+    config::store(&cfg)?;
+    if let Some(reference) = previous {
+        secret::delete(&reference)?;
+    }
+
+    match secret {
+        Some(_) => println!("info: stored the authenticator secret of account: {name}"),
+        None => println!("info: removed the authenticator secret of account: {name}"),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use std::{
+    io::Write,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use roblox_api::{
+    ApiError,
+    challenge::{Challenge, ChallengeType},
+    client::Client,
+};
+
+use crate::{
+    config::Account,
+    error::{Context, Error, Result},
+    secret::{self, totp},
+    web::{
+        WebClient,
+        two_step::{self, Channel},
+    },
+};
+
+/// Runs `request`, and when roblox answers it with a two step verification challenge, completes
+/// the challenge and runs it once more. The code comes from the authenticator secret stored for
+/// `account`, or is asked for.
+///
+/// `request` gets both clients, the completed challenge is sent back by the one it came from
+pub(crate) async fn retry<T>(
+    client: &mut Client,
+    web: &mut WebClient,
+    account: Option<&Account>,
+    mut request: impl AsyncFnMut(&mut Client, &mut WebClient) -> Result<T, roblox_api::Error>,
+) -> Result<T> {
+    let challenge = match request(client, web).await {
+        Err(roblox_api::Error::ApiError(ApiError::ChallengeRequired(challenge)))
+            if challenge.kind == ChallengeType::TwoStepVerification =>
+        {
+            challenge
+        }
+
+        result => return Ok(result?),
+    };
+
+    let from_web = web.challenged();
+    let token = verify(web, account, &challenge).await?;
+
+    match from_web {
+        true => web.queue_challenge(&challenge, &token).await,
+        false => client.queue_challenge(&challenge, &token).await,
+    }
+    .context("failed to continue two step verification")?;
+
+    Ok(request(client, web).await?)
+}
+
+async fn verify(
+    web: &mut WebClient,
+    account: Option<&Account>,
+    challenge: &Challenge,
+) -> Result<String> {
+    // Read out of the challenge, since there's no account to ask when logging in
+    let user_id = serde_json::to_value(&challenge.metadata)
+        .ok()
+        .and_then(|metadata| metadata["userId"].as_str()?.parse::<u64>().ok())
+        .ok_or(Error::Api(
+            "unexpected two step verification challenge".to_string(),
+        ))?;
+
+    let challenge_id = &challenge.metadata.server_challenge_id;
+    let action_type = challenge.metadata.action_type.to_string();
+
+    let (channel, code) = match account.map(secret::load_totp).transpose()?.flatten() {
+        Some(secret) => {
+            let time = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+
+            (Channel::Authenticator, totp::code(&secret, time)?)
+        }
+
+        None => {
+            eprintln!("info: roblox asks for two step verification");
+            let code = read_code("Authenticator code, leave empty to have one emailed: ")?;
+
+            if code.is_empty() {
+                two_step::send_email_code(web, user_id, challenge_id, &action_type)
+                    .await
+                    .context("failed to send the verification email")?;
+
+                (Channel::Email, read_code("Emailed code: ")?)
+            } else {
+                (Channel::Authenticator, code)
+            }
+        }
+    };
+
+    two_step::verify(web, user_id, channel, challenge_id, &action_type, &code)
+        .await
+        .context("failed to verify two step verification code")
+}
+
+/// Asks for a code on stderr, so stdout stays clean for the output of the command
+fn read_code(title: &str) -> Result<String> {
+    eprint!("{title}");
+    std::io::stderr()
+        .flush()
+        .context("failed to flush to stderr")?;
+
+    let mut code = String::new();
+    let read = std::io::stdin()
+        .read_line(&mut code)
+        .context("failed to read stdin")?;
+
+    if read == 0 {
+        return Err(Error::Auth(
+            "two step verification needs a code, store an authenticator secret with `account set-totp`"
+                .to_string(),
+        ));
+    }
+
+    Ok(code.split_whitespace().collect())
+}
@@ -13,12 +13,16 @@ use roblox_api::{
     client::Client,
 };
 
-use crate::{Config, config::DownloadPathKind};
+use crate::{
+    Config,
+    config::DownloadPathKind,
+    error::{Context, Error, Result},
+};
 
-fn download_to_file(bytes: &[u8], cfg: &Config, name: &str, extension: &str) {
+fn download_to_file(bytes: &[u8], cfg: &Config, name: &str, extension: &str) -> Result<()> {
     let unix = SystemTime::now()
         .duration_since(SystemTime::UNIX_EPOCH)
-        .unwrap()
+        .unwrap_or_default()
         .as_secs()
         .to_string();
 
@@ -30,11 +34,17 @@ fn download_to_file(bytes: &[u8], cfg: &Config, name: &str, extension: &str) {
 
     let local_download_path = download_path.join(&file_name);
 
-    let mut file = File::create_new(&local_download_path).unwrap();
-    file.write_all(bytes).unwrap();
+    let mut file = File::create_new(&local_download_path).context(&format!(
+        "failed to create {}",
+        local_download_path.display()
+    ))?;
+    file.write_all(bytes).context(&format!(
+        "failed to write {}",
+        local_download_path.display()
+    ))
 }
 
-pub(crate) async fn asset(client: &mut Client, cfg: &Config, id: u64) {
+pub(crate) async fn asset(client: &mut Client, cfg: &Config, id: u64) -> Result<()> {
     let mut inferer = Infer::new();
 
     fn rbxm_matcher(bytes: &[u8]) -> bool {
@@ -53,24 +63,32 @@ pub(crate) async fn asset(client: &mut Client, cfg: &Config, id: u64) {
     inferer.add("custom/rbxm", "rbxm", rbxm_matcher);
     inferer.add("custom/rbxmx", "rbxmx", rbxmx_matcher);
 
-    let asset_bytes = asset_delivery::v1::asset(client, id).await.unwrap();
-    let asset_kind = inferer.get(&asset_bytes).expect("Unknown file type");
+    let asset_bytes = asset_delivery::v1::asset(client, id)
+        .await
+        .context("failed to download asset")?;
+    let asset_kind = inferer
+        .get(&asset_bytes)
+        .ok_or(Error::Api("asset has an unknown file type".to_string()))?;
 
     let (kind, bytes) = match asset_kind.mime_type() {
         "application/gzip" => {
             let mut decoder = GzDecoder::new(asset_bytes.as_slice());
 
             let mut bytes = Vec::new();
-            decoder.read_to_end(&mut bytes).unwrap();
+            decoder
+                .read_to_end(&mut bytes)
+                .context("failed to decompress asset")?;
 
-            let kind = inferer.get(&bytes).expect("Unknown file type");
+            let kind = inferer
+                .get(&bytes)
+                .ok_or(Error::Api("asset has an unknown file type".to_string()))?;
             (kind, bytes)
         }
 
         _ => (asset_kind, asset_bytes),
     };
 
-    download_to_file(&bytes, cfg, &id.to_string(), kind.extension());
+    download_to_file(&bytes, cfg, &id.to_string(), kind.extension())
 }
 
 pub(crate) async fn thumbnail(
@@ -79,7 +97,7 @@ pub(crate) async fn thumbnail(
     id: u64,
     kind: ThumbnailRequestType,
     size: ThumbnailSize,
-) {
+) -> Result<()> {
     let format = ThumbnailFormat::Png;
 
     let thumbnails = thumbnails::v1::batch(
@@ -96,15 +114,18 @@ pub(crate) async fn thumbnail(
         }],
     )
     .await
-    .unwrap();
+    .context("failed to get thumbnail")?;
 
-    let thumbnail = thumbnails.first().unwrap();
+    let thumbnail = thumbnails
+        .first()
+        .ok_or(Error::NotFound("thumbnail not found".to_string()))?;
     let bytes = reqwest::get(&thumbnail.image_url)
         .await
-        .unwrap()
+        .and_then(|response| response.error_for_status())
+        .context("failed to download thumbnail")?
         .bytes()
         .await
-        .unwrap();
+        .context("failed to download thumbnail")?;
 
-    download_to_file(&bytes, cfg, &id.to_string(), format.extension());
+    download_to_file(&bytes, cfg, &id.to_string(), format.extension())
 }
@@ -1,4 +1,3 @@
-use console::style;
 use roblox_api::{
     api::{
         assets::{self, v1::Creator},
@@ -10,32 +9,45 @@ use roblox_api::{
 };
 
 use crate::{
+    error::{Context, Error, Result},
     object,
     object::{FieldStyle, Value},
     objects::badge::Badge,
+    output,
 };
 
-pub(crate) async fn user(client: &mut Client, id: u64) {
-    let info = users::v1::user_details(client, id).await.unwrap();
-    let is_premium = premium_features::v1::is_premium(client, id).await.unwrap();
+pub(crate) async fn user(client: &mut Client, id: u64) -> Result<()> {
+    let info = users::v1::user_details(client, id)
+        .await
+        .context("failed to get user details")?;
+    let is_premium = premium_features::v1::is_premium(client, id)
+        .await
+        .context("failed to get user premium status")?;
 
-    let presences = presence::v1::presence(client, &[id]).await.unwrap();
-    let presence = presences.first().unwrap();
+    let presences = presence::v1::presence(client, &[id])
+        .await
+        .context("failed to get user presence")?;
+    let presence = presences
+        .first()
+        .ok_or(Error::NotFound("user presence not found".to_string()))?;
 
     let object = object!(
         ("User", info.name),
         ("Display name", info.display_name),
-        ("Creation date", info.created.to_string()),
+        ("Creation date", info.created),
         ("Premium", is_premium),
         ("Presence", presence.status.to_owned()),
         ("About", info.description, FieldStyle::Description),
     );
 
-    print!("{}", object);
+    output::print(&object);
+    Ok(())
 }
 
-pub(crate) async fn group(client: &mut Client, id: u64) {
-    let info = groups::v1::information(client, id).await.unwrap();
+pub(crate) async fn group(client: &mut Client, id: u64) -> Result<()> {
+    let info = groups::v1::information(client, id)
+        .await
+        .context("failed to get group information")?;
 
     let owner_field = match info.owner {
         Some(owner) => Value::from(object!(
@@ -44,7 +56,7 @@ pub(crate) async fn group(client: &mut Client, id: u64) {
             ("Display name", owner.display_name),
         )),
 
-        None => Value::from("None"),
+        None => Value::Null,
     };
 
     let shout_field = match info.shout {
@@ -55,11 +67,11 @@ pub(crate) async fn group(client: &mut Client, id: u64) {
                 ("Name", shout.poster.name),
                 ("Display name", shout.poster.display_name),
             }),
-            ("Posted at", shout.created.to_string()),
-            ("Updated at", shout.updated.to_string()),
+            ("Posted at", shout.created),
+            ("Updated at", shout.updated),
         )),
 
-        None => Value::from("None"),
+        None => Value::Null,
     };
 
     let object = object!(
@@ -72,11 +84,14 @@ pub(crate) async fn group(client: &mut Client, id: u64) {
         ("About", info.description, FieldStyle::Description),
     );
 
-    print!("{}", object);
+    output::print(&object);
+    Ok(())
 }
 
-pub(crate) async fn asset(client: &mut Client, id: u64) {
-    let info = assets::v1::asset(client, id).await.unwrap();
+pub(crate) async fn asset(client: &mut Client, id: u64) -> Result<()> {
+    let info = assets::v1::asset(client, id)
+        .await
+        .context("failed to get asset")?;
 
     let owner_id = match info.creation_context.creator {
         Creator::UserId(id) => id,
@@ -87,35 +102,33 @@ pub(crate) async fn asset(client: &mut Client, id: u64) {
         ("Asset", info.name),
         ("Path", info.path),
         ("State", info.state),
-        ("Kind", info.asset_type.to_string()),
+        ("Kind", Value::Enum(info.asset_type.to_string())),
         ("Owner", { ("Id", owner_id) }),
         ("About", info.description, FieldStyle::Description),
     );
 
-    print!("{}", object);
+    output::print(&object);
+    Ok(())
 }
 
-pub(crate) async fn place(client: &mut Client, id: u64) {
-    let info = games::v1::batch_place_details(client, &[id]).await.unwrap();
-    let info = match info.first() {
-        Some(info) => info,
-        None => {
-            return eprintln!(
-                "{} {}",
-                style("error:").red().bold(),
-                style("game not found").bold()
-            );
-        }
-    };
+pub(crate) async fn place(client: &mut Client, id: u64) -> Result<()> {
+    let info = games::v1::batch_place_details(client, &[id])
+        .await
+        .context("failed to get place details")?;
+    let info = info
+        .first()
+        .ok_or(Error::NotFound("game not found".to_string()))?;
 
     let votes = games::v1::universe_votes(client, &[info.universe_id])
         .await
-        .unwrap();
-    let votes = votes.first().unwrap();
+        .context("failed to get game votes")?;
+    let votes = votes
+        .first()
+        .ok_or(Error::NotFound("game votes not found".to_string()))?;
 
     let favorites_count = games::v1::universe_favorite_count(client, info.universe_id)
         .await
-        .unwrap();
+        .context("failed to get game favorite count")?;
 
     let object = object!(
         ("Game", info.name.to_owned()),
@@ -124,8 +137,8 @@ pub(crate) async fn place(client: &mut Client, id: u64) {
         ("Playable", info.is_playable),
         ("Rating", {
             ("Favorites", favorites_count),
-            ("Likes", votes.likes.to_string()),
-            ("Disikes", votes.dislikes.to_string()),
+            ("Likes", votes.likes),
+            ("Disikes", votes.dislikes),
         }),
         ("Owner", {
             ("Id", info.builder_id),
@@ -134,36 +147,39 @@ pub(crate) async fn place(client: &mut Client, id: u64) {
         ("About", info.description.to_owned(), FieldStyle::Description),
     );
 
-    print!("{}", object);
+    output::print(&object);
+    Ok(())
 }
 
-pub(crate) async fn badge(client: &mut Client, id: u64) {
+pub(crate) async fn badge(client: &mut Client, id: u64) -> Result<()> {
     let badge = badges::v1::information(client, id)
         .await
-        .expect("error: failed to get information");
+        .context("failed to get information")?;
 
-    print!("{}", Badge::from_badge(badge));
+    output::print(&Badge::from_badge(badge));
+    Ok(())
 }
 
-pub(crate) async fn gamepass(client: &mut Client, id: u64) {
+pub(crate) async fn gamepass(client: &mut Client, id: u64) -> Result<()> {
     let info = gamepasses::v1::details(client, id)
         .await
-        .expect("error: failed to get gamepass details");
+        .context("failed to get gamepass details")?;
 
     let object = object!(("Badge", {
         ("Id", info.id),
         ("Name", info.name.to_owned()),
-        ("On sale", info.on_sale.to_string()),
+        ("On sale", info.on_sale),
         ("Price", info.price_information.unwrap_or(PriceInformation {
             enabled_features: Vec::new(),
             price_in_robux: 0,
         }).price_in_robux, FieldStyle::Price),
         ("Place Id", info.place_id),
         ("Icon image Id", info.icon_image_id),
-        ("Creation date", info.created.to_string()),
-        ("Last updated", info.updated.to_string()),
+        ("Creation date", info.created),
+        ("Last updated", info.updated),
         ("Description", info.description.to_owned(), FieldStyle::Description),
     }));
 
-    print!("{}", object);
+    output::print(&object);
+    Ok(())
 }
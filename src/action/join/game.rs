@@ -1,46 +1,130 @@
-use std::{fs::File, io::Write, process::Command};
+use chrono::Utc;
+use clap::ValueEnum;
+use roblox_api::{Paging, client::Client};
+use url::Url;
 
-use crate::config::Account;
+use crate::{
+    action::challenge,
+    config::Account,
+    error::{Context, Error, Result},
+    launcher::Launcher,
+    web::{self, WebClient, games::Server},
+};
 
-const SOBER_ROBLOX_CLIENT: &str = "org.vinegarhq.Sober";
+/// Stands in for the authentication ticket when the URI is only printed
+const TICKET_PLACEHOLDER: &str = "<ticket>";
 
-fn launch_url(id: u64, job_id: Option<&str>, private_server_code: Option<&str>) -> String {
-    let auth_ticket = "";
-    let browser_id = 0;
+/// How many pages of public servers are looked through when picking one
+const PICK_PAGES: usize = 5;
 
-    let game_id = if let Some(job_id) = job_id {
-        &format!("&gameId={job_id}")
-    } else {
-        ""
+/// Which public server `--pick` joins
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub(crate) enum Pick {
+    /// The one with the fewest players
+    Smallest,
+    /// The fullest one which still has room
+    Largest,
+    /// The one whose players have the lowest ping
+    LowestPing,
+    Random,
+}
+
+/// Where a link shared from roblox leads
+#[derive(Debug, PartialEq, Eq)]
+enum ShareLink {
+    /// `roblox.com/share?code=...&type=...`, only roblox knows what it points at
+    Share { code: String, kind: String },
+    /// `roblox.com/games/<place>/...?privateServerLinkCode=...`, how private servers used to be shared
+    PrivateServer { place_id: u64, link_code: String },
+}
+
+fn parse_link(link: &str) -> Result<ShareLink> {
+    let invalid = || Error::Config(format!("not a roblox share link: {link}"));
+
+    let url = Url::parse(link)
+        .or_else(|_| Url::parse(&format!("https://{link}")))
+        .map_err(|_| invalid())?;
+
+    if !url
+        .host_str()
+        .is_some_and(|host| host == "roblox.com" || host.ends_with(".roblox.com"))
+    {
+        return Err(invalid());
+    }
+
+    let query = |key: &str| {
+        url.query_pairs()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.into_owned())
     };
 
-    let link_code = if let Some(link_code) = private_server_code {
-        &format!("&linkCode={link_code}")
-    } else {
-        ""
+    let mut segments = url.path_segments().ok_or_else(invalid)?;
+    match segments.next() {
+        Some("share") => Ok(ShareLink::Share {
+            code: query("code").ok_or_else(invalid)?,
+            kind: query("type").ok_or_else(invalid)?,
+        }),
+
+        Some("games") => Ok(ShareLink::PrivateServer {
+            place_id: segments
+                .next()
+                .and_then(|id| id.parse().ok())
+                .ok_or_else(invalid)?,
+            link_code: query("privateServerLinkCode").ok_or_else(invalid)?,
+        }),
+
+        _ => Err(invalid()),
+    }
+}
+
+/// The percent encoded PlaceLauncher request for `id`, roblox ignores the job id and link code
+/// unless the request type asks for them
+fn place_launcher_url(
+    browser_id: u64,
+    id: u64,
+    job_id: Option<&str>,
+    private_server_code: Option<&str>,
+) -> String {
+    let (request, target) = match (job_id, private_server_code) {
+        (_, Some(link_code)) => ("RequestPrivateGame", format!("&linkCode={link_code}")),
+        (Some(job_id), None) => ("RequestGameJob", format!("&gameId={job_id}")),
+        (None, None) => ("RequestGame", String::new()),
     };
 
-    let place_launcher_url: String = url::form_urlencoded::byte_serialize(
+    url::form_urlencoded::byte_serialize(
         [
-            "https://www.roblox.com/Game/PlaceLauncher.ashx?request=RequestGame",
+            "https://www.roblox.com/Game/PlaceLauncher.ashx?request=",
+            request,
             &format!("&browserTrackerId={browser_id}"),
             &format!("&placeId={id}"),
             "&isPlayTogetherGame=false",
             "&joinAttemptOrigin=PlayButton",
-            game_id,
-            link_code,
+            &target,
         ]
         .concat()
         .as_bytes(),
     )
-    .collect();
+    .collect()
+}
+
+/// What gets a `roblox-player:` handler into the game, it logs in with `auth_ticket`
+fn launch_url(
+    auth_ticket: &str,
+    browser_id: u64,
+    id: u64,
+    job_id: Option<&str>,
+    private_server_code: Option<&str>,
+) -> String {
+    let launch_time = Utc::now().timestamp_millis();
+    let place_launcher_url = place_launcher_url(browser_id, id, job_id, private_server_code);
 
     [
         "roblox-player:1",
         "launchmode:play",
         &format!("gameinfo:{auth_ticket}"),
-        "launchtime:0",
+        &format!("launchtime:{launch_time}"),
         &format!("placelauncherurl:{place_launcher_url}"),
+        &format!("browsertrackerid:{browser_id}"),
         "baseUrl:https://www.roblox.com/",
         "channel:",
         "robloxLocale:en_us",
@@ -50,43 +134,230 @@ fn launch_url(id: u64, job_id: Option<&str>, private_server_code: Option<&str>)
     .join("+")
 }
 
-pub(crate) fn run(account: &Account, id: u64, job_id: Option<&str>) {
-    #[cfg(target_family = "windows")]
-    todo!("Make a pull request, I'm not sure which xdg utils windows has");
-
-    // Assuming we are using sober, we need to manually update the cookie file
-    let roblox_client = Command::new("xdg-mime")
-        .args(&["query", "default", "x-scheme-handler/roblox"])
-        .output()
-        .ok();
-
-    if let Some(roblox_client) = roblox_client {
-        if String::from_utf8_lossy(&roblox_client.stdout)
-            .to_string()
-            .starts_with(SOBER_ROBLOX_CLIENT)
-        {
-            const SOBER_COOKIES_PATH: &str = ".var/app/org.vinegarhq.Sober/data/sober/cookies";
-
-            let home = dirs::home_dir().unwrap();
-            let mut cookie_file = File::options()
-                .create(true)
-                .write(true)
-                .open(home.join(SOBER_COOKIES_PATH))
-                .unwrap();
-
-            cookie_file
-                .write_all(format!(".ROBLOSECURITY={}; ", account.cookie).as_bytes())
-                .unwrap();
+/// The server `pick` selects out of `servers`, `random` is only used by `Pick::Random`
+fn choose(servers: &[Server], pick: Pick, random: usize) -> Option<&Server> {
+    match pick {
+        Pick::Smallest => servers.iter().min_by_key(|server| server.playing),
+        Pick::Largest => servers.iter().max_by_key(|server| server.playing),
+        // Servers which don't report their ping yet are only picked when no other one does
+        Pick::LowestPing => servers
+            .iter()
+            .min_by_key(|server| server.ping.unwrap_or(u32::MAX)),
+        Pick::Random => servers.get(random % servers.len().max(1)),
+    }
+}
+
+/// Job id of the public server of the place `id` which `pick` selects
+pub(crate) async fn pick_server(web: &mut WebClient, id: u64, pick: Pick) -> Result<String> {
+    let mut servers = Vec::new();
+    let mut cursor = None;
+
+    for _ in 0..PICK_PAGES {
+        let page = web::games::public_servers(
+            web,
+            id,
+            true,
+            Paging::new(cursor.as_deref(), Some(100), None),
+        )
+        .await
+        .context("failed to get servers")?;
+
+        servers.extend(page.servers);
+        cursor = page.next_cursor.filter(|cursor| !cursor.is_empty());
+        if cursor.is_none() {
+            break;
         }
     }
 
+    let mut random = [0; 8];
+    openssl::rand::rand_bytes(&mut random)
+        .map_err(|error| Error::Io(format!("failed to pick a random server: {error}")))?;
+
+    choose(&servers, pick, usize::from_ne_bytes(random))
+        .map(|server| server.job_id.to_owned())
+        .ok_or(Error::NotFound(
+            "no public server with room to join".to_string(),
+        ))
+}
+
+/// Joins the private server a share link leads to
+pub(crate) async fn link(
+    client: &mut Client,
+    web: &mut WebClient,
+    account: Option<&Account>,
+    launcher: &dyn Launcher,
+    link: &str,
+) -> Result<()> {
+    let (place_id, link_code) = match parse_link(link)? {
+        ShareLink::PrivateServer {
+            place_id,
+            link_code,
+        } => (place_id, link_code),
+
+        ShareLink::Share { code, kind } => {
+            if !kind.eq_ignore_ascii_case("Server") {
+                return Err(Error::Config(format!(
+                    "share links of type: {kind} don't lead to a server"
+                )));
+            }
+
+            let invite = web::share_links::resolve(web, &code, &kind)
+                .await
+                .context("failed to resolve share link")?
+                .private_server_invite_data
+                .ok_or(Error::NotFound(
+                    "share link doesn't lead to a private server".to_string(),
+                ))?;
+
+            if invite.status != "Valid" {
+                return Err(Error::NotFound(format!(
+                    "share link is {}",
+                    invite.status.to_lowercase()
+                )));
+            }
+
+            invite.place_id.zip(invite.link_code).ok_or(Error::Api(
+                "unexpected response when resolving share link".to_string(),
+            ))?
+        }
+    };
+
+    run(
+        client,
+        web,
+        account,
+        launcher,
+        place_id,
+        None,
+        Some(&link_code),
+    )
+    .await
+}
+
+/// Launches the game with `launcher`, logged in as the account of `web`
+pub(crate) async fn run(
+    client: &mut Client,
+    web: &mut WebClient,
+    account: Option<&Account>,
+    launcher: &dyn Launcher,
+    id: u64,
+    job_id: Option<&str>,
+    private_server_code: Option<&str>,
+) -> Result<()> {
+    let auth_ticket = match launcher.needs_ticket() {
+        true => challenge::retry(client, web, account, async |_, web| {
+            web::auth::authentication_ticket(web).await
+        })
+        .await
+        .context("failed to get authentication ticket")?,
+        false => TICKET_PLACEHOLDER.to_string(),
+    };
+
+    // Only used by roblox to tell launches from the same browser apart
+    let mut browser_id = [0; 4];
+    openssl::rand::rand_bytes(&mut browser_id)
+        .map_err(|error| Error::Io(format!("failed to generate a browser id: {error}")))?;
+
     // TODO: lock file
 
-    #[cfg(target_family = "unix")]
-    Command::new("xdg-open")
-        .arg(launch_url(id, job_id, None))
-        .spawn()
-        .expect("error: failed to launch roblox")
-        .wait()
-        .unwrap();
+    launcher.launch(&launch_url(
+        &auth_ticket,
+        u32::from_ne_bytes(browser_id).into(),
+        id,
+        job_id,
+        private_server_code,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Pick, ShareLink, choose, launch_url, parse_link, place_launcher_url};
+    use crate::web::games::Server;
+
+    #[test]
+    fn picked_servers() {
+        let server = |job_id: &str, playing, ping| Server {
+            job_id: job_id.to_string(),
+            max_players: 10,
+            playing,
+            fps: Some(60.0),
+            ping,
+        };
+
+        let servers = [
+            server("a", 4, Some(80)),
+            server("b", 1, None),
+            server("c", 9, Some(40)),
+        ];
+
+        let pick = |pick| choose(&servers, pick, 4).map(|server| server.job_id.as_str());
+        assert_eq!(pick(Pick::Smallest), Some("b"));
+        assert_eq!(pick(Pick::Largest), Some("c"));
+        assert_eq!(pick(Pick::LowestPing), Some("c"));
+        assert_eq!(pick(Pick::Random), Some("b"));
+        assert_eq!(choose(&[], Pick::Random, 4).map(|x| x.job_id.clone()), None);
+    }
+
+    #[test]
+    fn picked_server_launch() {
+        let servers = [Server {
+            job_id: "a1-b2".to_string(),
+            max_players: 10,
+            playing: 3,
+            fps: None,
+            ping: Some(40),
+        }];
+
+        let job_id = choose(&servers, Pick::LowestPing, 0).map(|server| server.job_id.as_str());
+        let url = launch_url("<ticket>", 7, 1818, job_id, None);
+        assert!(url.contains("%3Frequest%3DRequestGameJob%26"));
+        assert!(url.contains("%26gameId%3Da1-b2+"));
+    }
+
+    #[test]
+    fn launch_requests() {
+        let url = |request: &str, target: &str| {
+            format!(
+                "https%3A%2F%2Fwww.roblox.com%2FGame%2FPlaceLauncher.ashx%3Frequest%3D{request}\
+                 %26browserTrackerId%3D7%26placeId%3D1818%26isPlayTogetherGame%3Dfalse\
+                 %26joinAttemptOrigin%3DPlayButton{target}"
+            )
+        };
+
+        assert_eq!(
+            place_launcher_url(7, 1818, None, None),
+            url("RequestGame", "")
+        );
+        assert_eq!(
+            place_launcher_url(7, 1818, Some("a1-b2"), None),
+            url("RequestGameJob", "%26gameId%3Da1-b2")
+        );
+        assert_eq!(
+            place_launcher_url(7, 1818, None, Some("42")),
+            url("RequestPrivateGame", "%26linkCode%3D42")
+        );
+    }
+
+    #[test]
+    fn share_links() {
+        assert_eq!(
+            parse_link("https://www.roblox.com/share?code=7a1c2b&type=Server").unwrap(),
+            ShareLink::Share {
+                code: "7a1c2b".to_string(),
+                kind: "Server".to_string()
+            }
+        );
+
+        assert_eq!(
+            parse_link("roblox.com/games/1818/Classic-Crossroads?privateServerLinkCode=42")
+                .unwrap(),
+            ShareLink::PrivateServer {
+                place_id: 1818,
+                link_code: "42".to_string()
+            }
+        );
+
+        assert!(parse_link("https://example.com/share?code=7a1c2b&type=Server").is_err());
+        assert!(parse_link("https://www.roblox.com/share?type=Server").is_err());
+    }
 }
@@ -1,8 +1,101 @@
-use roblox_api::{api::groups, client::Client};
+use roblox_api::{
+    api::{groups, presence, users},
+    client::Client,
+};
+
+use super::challenge;
+use crate::{
+    config::Account,
+    error::{Context, Error, Result},
+    launcher::Launcher,
+    web::WebClient,
+};
 
 pub(crate) mod game;
 
-pub(crate) async fn group(client: &mut Client, id: u64) {
-    client.ensure_token().await.unwrap();
-    groups::v1::join(client, id).await.unwrap();
+pub(crate) async fn group(
+    client: &mut Client,
+    web: &mut WebClient,
+    account: Option<&Account>,
+    id: u64,
+) -> Result<()> {
+    challenge::retry(client, web, account, async |client, _| {
+        groups::v1::join(client, id).await
+    })
+    .await
+    .context("failed to join group")
+}
+
+/// Id of `user`, which is either an id or a username
+async fn user_id(client: &mut Client, user: &str) -> Result<u64> {
+    if let Ok(id) = user.parse() {
+        return Ok(id);
+    }
+
+    users::v1::users_by_name(client, &[user], false)
+        .await
+        .context("failed to look up user")?
+        .first()
+        .map(|user| user.id)
+        .ok_or(Error::NotFound(format!("user: {user} not found")))
+}
+
+/// Joins the server `user` is playing in
+pub(crate) async fn user(
+    client: &mut Client,
+    web: &mut WebClient,
+    account: Option<&Account>,
+    launcher: &dyn Launcher,
+    user: &str,
+) -> Result<()> {
+    let id = user_id(client, user).await?;
+    let presence = presence::v1::presence(client, &[id])
+        .await
+        .context("failed to get presence")?
+        .into_iter()
+        .next()
+        .ok_or(Error::NotFound(format!(
+            "presence of user: {user} not found"
+        )))?;
+
+    // Offline, Online, InGame, InStudio and Invisible, in the order roblox numbers them
+    match presence.kind {
+        2 => {}
+        3 => {
+            return Err(Error::NotFound(format!(
+                "user: {user} is in studio, not a game"
+            )));
+        }
+        1 => {
+            return Err(Error::NotFound(format!(
+                "user: {user} is online, but not in a game"
+            )));
+        }
+        _ => return Err(Error::NotFound(format!("user: {user} is offline"))),
+    }
+
+    // Roblox leaves the server out when the user doesn't let the account join them
+    match (presence.place_id, presence.job_id) {
+        (Some(place_id), Some(job_id)) => {
+            match presence.status.as_str() {
+                "" => eprintln!("info: joining {user}"),
+                game => eprintln!("info: joining {user} in {game}"),
+            }
+
+            game::run(
+                client,
+                web,
+                account,
+                launcher,
+                place_id,
+                Some(&job_id),
+                None,
+            )
+            .await
+        }
+
+        _ => Err(Error::Private(format!(
+            "user: {user} is in a game, but has joins turned off for this account"
+        ))),
+    }
 }
@@ -2,58 +2,68 @@ use roblox_api::{
     Paging,
     api::{
         badges::{self, v1::BadgesResponse},
-        games, users,
+        games,
     },
     client::Client,
 };
 
-use crate::{object, object::Value, objects::badge::Badge};
+use crate::{
+    action::user_id,
+    command::list::PagingArgs,
+    error::{Context, Error, Result},
+    object::Value,
+    objects::badge::Badge,
+};
+
+use super::{Page, paginate};
 
-fn object_printer(result: BadgesResponse) {
+fn badge_page(result: BadgesResponse) -> Page {
     let badges: Vec<Value> = result
         .badges
         .into_iter()
         .map(|badge| Value::from(Badge::from_badge(badge)))
         .collect();
 
-    let object = object!(
-        (
-            "Next cursor",
-            result.next_cursor.unwrap_or_default().to_owned()
-        ),
-        (
-            "Previous cursor",
-            result.previous_cursor.unwrap_or_default().to_owned()
-        ),
-        ("Badges", badges)
-    );
-
-    print!("{}", object);
+    Page {
+        entries: badges,
+        next_cursor: result.next_cursor,
+        previous_cursor: result.previous_cursor,
+    }
 }
 
-pub(crate) async fn place(client: &mut Client, place_id: u64) {
+pub(crate) async fn place(client: &mut Client, place_id: u64, paging: &PagingArgs) -> Result<()> {
     let place_details = games::v1::batch_place_details(client, &[place_id])
         .await
-        .unwrap();
-    let place_details = place_details.first().unwrap();
-
-    let result = badges::v1::universe_badges(
-        client,
-        place_details.universe_id,
-        None,
-        Paging::new(None, Some(100), None),
-    )
-    .await
-    .expect("error: failed to get place badges");
+        .context("failed to get place details")?;
+    let universe_id = place_details
+        .first()
+        .ok_or(Error::NotFound("game not found".to_string()))?
+        .universe_id;
 
-    object_printer(result);
+    paginate(paging, "Badges", 100, 100, async |cursor, limit| {
+        Ok(badge_page(
+            badges::v1::universe_badges(
+                client,
+                universe_id,
+                None,
+                Paging::new(cursor, Some(limit), None),
+            )
+            .await
+            .context("failed to get place badges")?,
+        ))
+    })
+    .await
 }
 
-pub(crate) async fn user(client: &mut Client, id: Option<u64>) {
-    let id = id.unwrap_or(users::v1::authenticated_details(client).await.unwrap().id);
-    let result = badges::v1::user_badges(client, id, Paging::default())
-        .await
-        .expect("error: failed to get user badges");
+pub(crate) async fn user(client: &mut Client, id: Option<u64>, paging: &PagingArgs) -> Result<()> {
+    let id = user_id(client, id).await?;
 
-    object_printer(result);
+    paginate(paging, "Badges", 10, 100, async |cursor, limit| {
+        Ok(badge_page(
+            badges::v1::user_badges(client, id, Paging::new(cursor, Some(limit), None))
+                .await
+                .context("failed to get user badges")?,
+        ))
+    })
+    .await
 }
@@ -1,19 +1,26 @@
 use console::style;
 use roblox_api::{
     Paging,
-    api::{
-        games::{self, v2::GamesResponse},
-        users,
-    },
+    api::games::{self, v2::GamesResponse},
     client::Client,
 };
 
+use crate::action::user_id;
+use crate::command::list::PagingArgs;
+use crate::error::{Context, Result};
 use crate::object;
 use crate::object::{FieldStyle, Value};
 
-fn print_experience_creations(result: GamesResponse) {
+use super::{Page, paginate};
+
+/// The page of `result`, fetched after `cursor`, an empty one is pointed out on stderr
+fn experience_page(result: GamesResponse, cursor: Option<&str>) -> Page {
     if result.games.is_empty() {
-        return println!("{} entity has no experiences", style("info:").bold());
+        let message = match cursor {
+            Some(_) => "no more experiences after the cursor",
+            None => "entity has no experiences",
+        };
+        eprintln!("{} {message}", style("info:").bold());
     }
 
     let mut games = Vec::new();
@@ -26,37 +33,43 @@ fn print_experience_creations(result: GamesResponse) {
                     ("Id", creation.root_place.id),
                     ("Price", creation.price.unwrap_or_default(), FieldStyle::Price),
                     ("Visits", creation.place_visits),
-                    ("Creation date", creation.created.to_string()),
-                    ("Last updated", creation.updated.to_string()),
-                    ("About", creation.description.to_owned().unwrap_or(String::new()), FieldStyle::Description),
-                }), 
+                    ("Creation date", &creation.created),
+                    ("Last updated", &creation.updated),
+                    ("About", creation.description.to_owned(), FieldStyle::Description),
+                }),
             })
         )));
     }
 
-    let object = object!( 
-        ("Next cursor", result.next_cursor.unwrap_or_default().to_owned()),
-        ("Previous cursor", result.previous_cursor.unwrap_or_default().to_owned()),
-        ("Games", games)
-    );
-
-    print!("{}", object);
+    Page {
+        entries: games,
+        next_cursor: result.next_cursor,
+        previous_cursor: result.previous_cursor,
+    }
 }
 
-pub(crate) async fn group(client: &mut Client, id: u64) {
-    print_experience_creations(
-        games::v2::group_games_v2(client, id, 1, Paging::default())
-            .await
-            .expect("error: failed to get group experience creations"),
-    );
+pub(crate) async fn group(client: &mut Client, id: u64, paging: &PagingArgs) -> Result<()> {
+    paginate(paging, "Games", 10, 50, async |cursor, limit| {
+        Ok(experience_page(
+            games::v2::group_games_v2(client, id, 1, Paging::new(cursor, Some(limit), None))
+                .await
+                .context("failed to get group experience creations")?,
+            cursor,
+        ))
+    })
+    .await
 }
 
-pub(crate) async fn user(client: &mut Client, id: Option<u64>) {
-    let id = id.unwrap_or(users::v1::authenticated_details(client).await.unwrap().id);
+pub(crate) async fn user(client: &mut Client, id: Option<u64>, paging: &PagingArgs) -> Result<()> {
+    let id = user_id(client, id).await?;
 
-    print_experience_creations(
-        games::v2::user_games(client, id, 2, Paging::default())
-            .await
-            .expect("error: failed to get user experience creations"),
-    );
+    paginate(paging, "Games", 10, 50, async |cursor, limit| {
+        Ok(experience_page(
+            games::v2::user_games(client, id, 2, Paging::new(cursor, Some(limit), None))
+                .await
+                .context("failed to get user experience creations")?,
+            cursor,
+        ))
+    })
+    .await
 }
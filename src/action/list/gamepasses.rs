@@ -1,17 +1,33 @@
+use futures::{StreamExt, TryStreamExt};
 use roblox_api::{
     Paging,
-    api::{gamepasses, games, users},
-    client::Client,
+    api::{
+        gamepasses::{self, v1::GamepassDetails},
+        games,
+        inventory::{self, v1::ItemType},
+    },
+    client::{Client, Cookie},
 };
 
+use crate::action::user_id;
+use crate::command::list::PagingArgs;
+use crate::error::{Context, Error, Result};
 use crate::object;
-use crate::object::{FieldStyle, Value};
+use crate::object::{Field, FieldStyle, ObjectBuilder, Value};
+use crate::output;
 
-pub(crate) async fn place(client: &mut Client, place_id: u64) {
+use super::{Page, paginate};
+
+/// How many gamepasses of an inventory page are looked up at once
+const CONCURRENT_LOOKUPS: usize = 10;
+
+pub(crate) async fn place(client: &mut Client, place_id: u64) -> Result<()> {
     let place_details = games::v1::batch_place_details(client, &[place_id])
         .await
-        .unwrap();
-    let place_details = place_details.first().unwrap();
+        .context("failed to get place details")?;
+    let place_details = place_details
+        .first()
+        .ok_or(Error::NotFound("game not found".to_string()))?;
 
     let result = games::v1::universe_gamepasses(
         client,
@@ -19,7 +35,7 @@ pub(crate) async fn place(client: &mut Client, place_id: u64) {
         Paging::new(None, Some(100), None),
     )
     .await
-    .unwrap();
+    .context("failed to get place gamepasses")?;
 
     let mut gamepasses = Vec::new();
     for gamepass in &result.gamepasses {
@@ -27,7 +43,7 @@ pub(crate) async fn place(client: &mut Client, place_id: u64) {
             ("Id", gamepass.id),
             ("Name", gamepass.name.to_owned()),
             ("Display name", gamepass.display_name.to_owned()),
-            ("Price", gamepass.price.unwrap_or(0).to_string(), FieldStyle::Price),
+            ("Price", gamepass.price.unwrap_or(0), FieldStyle::Price),
             ("Owned", gamepass.owned),
         }));
 
@@ -46,15 +62,16 @@ pub(crate) async fn place(client: &mut Client, place_id: u64) {
         ("Gamepasses", gamepasses),
     );
 
-    print!("{}", object);
+    output::print(&object);
+    Ok(())
 }
 
-pub(crate) async fn user(client: &mut Client, id: Option<u64>) {
-    let id = id.unwrap_or(users::v1::authenticated_details(client).await.unwrap().id);
+pub(crate) async fn user(client: &mut Client, id: Option<u64>) -> Result<()> {
+    let id = user_id(client, id).await?;
 
     let result = gamepasses::v1::user_gamepasses(client, id, Paging::default())
         .await
-        .expect("error: failed to get user gamepasses");
+        .context("failed to get user gamepasses")?;
 
     let mut gamepasses = Vec::new();
     for gamepass in &result {
@@ -69,7 +86,7 @@ pub(crate) async fn user(client: &mut Client, id: Option<u64>) {
             ("Id", gamepass.id),
             ("Name", gamepass.name.to_owned()),
             ("On sale", gamepass.on_sale),
-            ("Price", gamepass.price.unwrap_or(0).to_string(), FieldStyle::Price),
+            ("Price", gamepass.price.unwrap_or(0), FieldStyle::Price),
             ("Creator", creator),
             ("About", gamepass.description.to_owned(), FieldStyle::Description),
         }));
@@ -78,5 +95,129 @@ pub(crate) async fn user(client: &mut Client, id: Option<u64>) {
     }
 
     let object = object!(("Gamepasses", gamepasses));
-    print!("{}", object);
+    output::print(&object);
+    Ok(())
+}
+
+/// The details of gamepass `id` and the Instance Id of the copy `user_id` owns, each lookup gets a
+/// client of its own so they can run at once
+async fn owned_details(
+    cookie: &str,
+    user_id: u64,
+    id: u64,
+) -> Result<(GamepassDetails, Option<u64>)> {
+    let mut client = Client::from_cookie(Cookie::from(cookie));
+
+    let details = gamepasses::v1::details(&mut client, id)
+        .await
+        .context("failed to get gamepass details")?;
+    let instance_id = inventory::v1::user_owns_assets(
+        &mut client,
+        user_id,
+        id,
+        ItemType::Gamepass,
+        Paging::default(),
+    )
+    .await
+    .context("failed to get gamepass ownership")?
+    .assets
+    .first()
+    .map(|asset| asset.instance_id);
+
+    Ok((details, instance_id))
+}
+
+/// Lists the gamepasses owned by `id` in the layout of `list inventory`, the cursor is the Id of
+/// the gamepass to continue after.
+///
+/// Unlike assets, roblox doesn't say when or how a gamepass was acquired, the Instance Id of the
+/// copy `id` owns is all there is
+pub(crate) async fn inventory(
+    client: &mut Client,
+    cookie: &str,
+    id: u64,
+    verbose: bool,
+    paging: &PagingArgs,
+) -> Result<()> {
+    paginate(paging, "Assets", 100, 100, async |cursor, limit| {
+        let result =
+            gamepasses::v1::user_gamepasses(client, id, Paging::new(cursor, Some(limit), None))
+                .await
+                .context("failed to get user gamepasses")?;
+
+        let details: Vec<(GamepassDetails, Option<u64>)> = futures::stream::iter(&result)
+            .map(|gamepass| owned_details(cookie, id, gamepass.id))
+            .buffered(CONCURRENT_LOOKUPS)
+            .try_collect()
+            .await?;
+
+        let mut place_ids: Vec<u64> = details
+            .iter()
+            .map(|(details, _)| details.place_id)
+            .collect();
+        place_ids.sort_unstable();
+        place_ids.dedup();
+
+        let places = if place_ids.is_empty() {
+            Vec::new()
+        } else {
+            games::v1::batch_place_details(client, &place_ids)
+                .await
+                .context("failed to get gamepass places")?
+        };
+
+        let mut assets = Vec::new();
+        for (gamepass, (details, instance_id)) in result.iter().zip(&details) {
+            let place = match places.iter().find(|place| place.id == details.place_id) {
+                Some(place) => Value::from(object!(
+                    ("Id", place.id),
+                    ("Name", place.name.to_owned()),
+                    ("Universe Id", place.universe_id),
+                )),
+
+                None => Value::from(object!(("Id", details.place_id))),
+            };
+
+            let mut builder = ObjectBuilder::default().with_field(Field::new(
+                "Asset",
+                Value::from(object!(
+                    ("Id", gamepass.id),
+                    ("Name", gamepass.name.to_owned()),
+                    ("Instance Id", *instance_id),
+                    ("Place", place),
+                    ("Price", gamepass.price, FieldStyle::Price),
+                    ("On sale", gamepass.on_sale),
+                )),
+            ));
+
+            if verbose {
+                builder = builder
+                    .with_field(Field::new(
+                        "Creator",
+                        Value::from(object!(
+                            ("Id", gamepass.creator.id),
+                            ("Name", gamepass.creator.name.to_owned()),
+                            ("Kind", Value::Enum(gamepass.creator.kind.to_string())),
+                        )),
+                    ))
+                    .with_field(Field::new("Creation date", Value::from(&details.created)))
+                    .with_field(Field::new("Last updated", Value::from(&details.updated)));
+            }
+
+            assets.push(Value::from(builder.build()));
+        }
+
+        // A full page means there might be more after its last gamepass
+        let next_cursor = match result.last() {
+            Some(gamepass) if result.len() >= limit as usize => Some(gamepass.id.to_string()),
+            _ => None,
+        };
+
+        Ok(Page {
+            entries: assets,
+            next_cursor,
+            previous_cursor: None,
+        })
+    })
+    .await
 }
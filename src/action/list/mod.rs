@@ -1,56 +1,218 @@
-use console::style;
-
 use roblox_api::{
     AssetTypeId, Paging, SortOrder,
     api::{
-        avatar, friends, groups, inventory, notifications, platform_chat,
+        avatar, friends, games, groups, inventory, notifications, platform_chat,
         private_messages::{self, v1::MessageTab},
-        users,
     },
     client::Client,
 };
 
+use crate::action::user_id;
+use crate::command::list::PagingArgs;
+use crate::error::{Context, Error, Result};
 use crate::object;
 use crate::object::{Field, FieldStyle, ObjectBuilder, Value};
+use crate::output;
+use crate::web::{self, WebClient, trades::TradeStatusType};
 
 pub(crate) mod badges;
 pub(crate) mod experiences;
 pub(crate) mod gamepasses;
 pub(crate) mod name_history;
 
-pub(crate) async fn favorites(client: &mut Client, id: Option<u64>, asset_kind: AssetTypeId) {
-    let id = id.unwrap_or(users::v1::authenticated_details(client).await.unwrap().id);
-    todo!()
+/// The page sizes the roblox apis accept
+const PAGE_SIZES: [u16; 4] = [10, 25, 50, 100];
+
+/// One page of a listing, already converted into printable entries
+pub(crate) struct Page {
+    pub(crate) entries: Vec<Value>,
+    pub(crate) next_cursor: Option<String>,
+    pub(crate) previous_cursor: Option<String>,
+}
+
+/// Prints the entries under `key` page by page as `fetch` returns them, following the next
+/// cursors as far as `paging` asks for.
+///
+/// `fetch` is called with the cursor and size of the page to get, without `--limit` or `--all`
+/// a single page of `page_size` entries is listed, otherwise pages of up to `max_page_size`
+pub(crate) async fn paginate(
+    paging: &PagingArgs,
+    key: &str,
+    page_size: u16,
+    max_page_size: u16,
+    mut fetch: impl AsyncFnMut(Option<&str>, u16) -> Result<Page>,
+) -> Result<()> {
+    let mut stream = output::Stream::default();
+    let mut cursor = paging.cursor.clone();
+    let mut remaining = paging.limit;
+
+    loop {
+        let size = match remaining {
+            Some(remaining) => PAGE_SIZES
+                .into_iter()
+                .find(|size| *size as usize >= remaining)
+                .unwrap_or(max_page_size)
+                .min(max_page_size),
+            None if paging.all => max_page_size,
+            None => page_size,
+        };
+
+        // Print what was already listed before failing
+        let page = match fetch(cursor.as_deref(), size).await {
+            Ok(page) => page,
+            Err(error) => {
+                stream.finish();
+                return Err(error);
+            }
+        };
+
+        let next_cursor = page.next_cursor.filter(|cursor| !cursor.is_empty());
+
+        let mut entries = page.entries;
+        if let Some(remaining) = &mut remaining {
+            entries.truncate(*remaining);
+            *remaining -= entries.len();
+        }
+
+        stream.push(&object!(
+            ("Next cursor", next_cursor.to_owned()),
+            (
+                "Previous cursor",
+                page.previous_cursor.filter(|cursor| !cursor.is_empty())
+            ),
+            (key, entries)
+        ));
+
+        let follow = match remaining {
+            Some(remaining) => remaining > 0,
+            None => paging.all,
+        };
+
+        match next_cursor {
+            Some(next_cursor) if follow => cursor = Some(next_cursor),
+            _ => break,
+        }
+    }
+
+    stream.finish();
+    Ok(())
+}
+
+pub(crate) async fn favorites(
+    client: &mut Client,
+    web: &mut WebClient,
+    id: Option<u64>,
+    asset_kind: AssetTypeId,
+    paging: &PagingArgs,
+) -> Result<()> {
+    let id = user_id(client, id).await?;
+
+    // Favorited experiences are tracked by the games api instead of the catalog
+    if matches!(asset_kind, AssetTypeId::Place) {
+        return paginate(paging, "Favorites", 10, 50, async |cursor, limit| {
+            let result = games::v2::user_favorited_games(
+                client,
+                id,
+                2,
+                Paging::new(cursor, Some(limit), Some(SortOrder::Descending)),
+            )
+            .await
+            .context("failed to get user favorited experiences")?;
+
+            let mut games = Vec::new();
+            for game in result.games {
+                games.push(Value::from(object!(("Asset", {
+                    ("Id", game.id),
+                    ("Name", game.name),
+                    ("Kind", Value::Enum(asset_kind.to_string())),
+                    ("Root place Id", game.root_place.id),
+                    ("Creator", {
+                        ("Id", game.creator.id),
+                        ("Name", game.creator.name),
+                        ("Kind", Value::Enum(game.creator.kind)),
+                    }),
+                    ("Price", game.price, FieldStyle::Price),
+                    ("Visits", game.place_visits),
+                }))));
+            }
+
+            Ok(Page {
+                entries: games,
+                next_cursor: result.next_cursor,
+                previous_cursor: result.previous_cursor,
+            })
+        })
+        .await;
+    }
+
+    paginate(paging, "Favorites", 10, 100, async |cursor, limit| {
+        let result = web::catalog::user_favorites(
+            web,
+            id,
+            asset_kind,
+            Paging::new(cursor, Some(limit), None),
+        )
+        .await
+        .context("failed to get user favorites")?;
+
+        let mut assets = Vec::new();
+        for asset in result.assets {
+            assets.push(Value::from(object!(("Asset", {
+                ("Id", asset.id),
+                ("Name", asset.name),
+                ("Kind", Value::Enum(asset_kind.to_string())),
+                ("Creator", {
+                    ("Id", asset.creator_target_id),
+                    ("Name", asset.creator_name),
+                    ("Kind", asset.creator_type.map(Value::Enum)),
+                    ("Is verified", asset.creator_has_verified_badge.unwrap_or(false)),
+                }),
+                ("Price", asset.price, FieldStyle::Price),
+                ("Lowest price", asset.lowest_price, FieldStyle::Price),
+                ("Favorites", asset.favorite_count),
+                ("About", asset.description, FieldStyle::Description),
+            }))));
+        }
+
+        Ok(Page {
+            entries: assets,
+            next_cursor: result.next_cursor,
+            previous_cursor: result.previous_cursor,
+        })
+    })
+    .await
 }
 
 pub(crate) async fn inventory(
     client: &mut Client,
+    cookie: &str,
     id: Option<u64>,
     asset_kind: AssetTypeId,
     verbose: bool,
-    _in_json: bool,
-) {
-    let id = id.unwrap_or(users::v1::authenticated_details(client).await.unwrap().id);
-
-    if !inventory::v1::can_view_inventory(client, id).await.unwrap() {
-        return eprintln!(
-            "{} {}",
-            style("error:").red().bold(),
-            style("user has private inventory").bold()
-        );
+    paging: &PagingArgs,
+) -> Result<()> {
+    let id = user_id(client, id).await?;
+
+    if !inventory::v1::can_view_inventory(client, id)
+        .await
+        .context("failed to check inventory visibility")?
+    {
+        return Err(Error::Private("user has private inventory".to_string()));
     };
 
-    let object = if matches!(asset_kind, AssetTypeId::Gamepass) {
-        object!()
-    } else {
+    if matches!(asset_kind, AssetTypeId::Gamepass) {
+        return gamepasses::inventory(client, cookie, id, verbose, paging).await;
+    }
+
+    paginate(paging, "Assets", 100, 100, async |cursor, limit| {
         let result = inventory::v2::user_owned_assets(
             client,
             id,
             asset_kind,
-            Paging::new(None, Some(100), Some(SortOrder::default())),
+            Paging::new(cursor, Some(limit), Some(SortOrder::default())),
         )
         .await
-        .unwrap();
+        .context("failed to get user inventory")?;
 
         let mut assets = Vec::new();
         for asset in &result.assets {
@@ -85,38 +247,28 @@ pub(crate) async fn inventory(
                         "Owner",
                         Value::from(object!(("Name", asset.owner.name.to_owned()))),
                     ))
-                    .with_field(Field::new(
-                        "Creation date",
-                        Value::from(asset.created.to_string()),
-                    ))
-                    .with_field(Field::new(
-                        "Last updated",
-                        Value::from(asset.updated.to_string()),
-                    ))
+                    .with_field(Field::new("Creation date", Value::from(&asset.created)))
+                    .with_field(Field::new("Last updated", Value::from(&asset.updated)))
             }
 
             assets.push(Value::from(builder.build()));
         }
 
-        object!(
-            ("Next cursor", result.next_cursor.unwrap_or_default()),
-            (
-                "Previous cursor",
-                result.previous_cursor.unwrap_or_default()
-            ),
-            ("Assets", assets)
-        )
-    };
-
-    print!("{}", object);
+        Ok(Page {
+            entries: assets,
+            next_cursor: result.next_cursor,
+            previous_cursor: result.previous_cursor,
+        })
+    })
+    .await
 }
 
-pub(crate) async fn groups(client: &mut Client, id: Option<u64>) {
-    let id = id.unwrap_or(users::v1::authenticated_details(client).await.unwrap().id);
+pub(crate) async fn groups(client: &mut Client, id: Option<u64>) -> Result<()> {
+    let id = user_id(client, id).await?;
 
     let result = groups::v1::user_roles(client, id)
         .await
-        .expect("error: failed to get user groups");
+        .context("failed to get user groups")?;
 
     for (info, role) in &result {
         let owner_field = match &info.owner {
@@ -126,7 +278,7 @@ pub(crate) async fn groups(client: &mut Client, id: Option<u64>) {
                 ("Display name", owner.display_name.to_owned()),
             )),
 
-            None => Value::from("None"),
+            None => Value::Null,
         };
 
         let shout_field = match &info.shout {
@@ -136,17 +288,17 @@ pub(crate) async fn groups(client: &mut Client, id: Option<u64>) {
                     ("Id", shout.poster.id),
                     ("Name", shout.poster.name.to_owned()),
                     ("Display name", shout.poster.display_name.to_owned()),
-                    ("Posted at", shout.created.to_string()),
-                    ("Updated at", shout.updated.to_string()),
+                    ("Posted at", &shout.created),
+                    ("Updated at", &shout.updated),
                 })
             )),
 
-            None => Value::from("None"),
+            None => Value::Null,
         };
 
         let group = object!(
             ("Group", info.name.to_owned()),
-            ("Members", info.member_count.unwrap_or(0).to_string()),
+            ("Members", info.member_count.unwrap_or(0)),
             ("Public", info.is_public),
             ("Premium only", info.premium_only),
             ("Owner", owner_field),
@@ -159,15 +311,17 @@ pub(crate) async fn groups(client: &mut Client, id: Option<u64>) {
             }),
         );
 
-        print!("{}", group);
+        output::print(&group);
     }
+
+    Ok(())
 }
 
-pub(crate) async fn avatar(client: &mut Client, id: Option<u64>) {
-    let id = id.unwrap_or(users::v1::authenticated_details(client).await.unwrap().id);
+pub(crate) async fn avatar(client: &mut Client, id: Option<u64>) -> Result<()> {
+    let id = user_id(client, id).await?;
     let avatar = avatar::v1::user_avatar(client, id)
         .await
-        .expect("error: failed to get user avatar");
+        .context("failed to get user avatar")?;
 
     let mut assets = Vec::new();
     for asset in avatar.assets {
@@ -191,36 +345,37 @@ pub(crate) async fn avatar(client: &mut Client, id: Option<u64>) {
     }
 
     let object = object!(
-        ("Avatar type", avatar.kind.to_string()),
+        ("Avatar type", Value::Enum(avatar.kind.to_string())),
         ("Default shirt", avatar.default_shirt_applied),
         ("Default pants", avatar.default_pants_applied),
         ("Scales", {
-            ("Height", avatar.scales.height.to_string()),
-            ("Width", avatar.scales.width.to_string()),
-            ("Head", avatar.scales.head.to_string()),
-            ("Depth", avatar.scales.depth.to_string()),
-            ("Proportion", avatar.scales.proportion.to_string()),
-            ("Body type", avatar.scales.body_type.to_string()),
+            ("Height", avatar.scales.height),
+            ("Width", avatar.scales.width),
+            ("Head", avatar.scales.head),
+            ("Depth", avatar.scales.depth),
+            ("Proportion", avatar.scales.proportion),
+            ("Body type", avatar.scales.body_type),
         }),
         ("Body colors", {
-            ("Head", avatar.body_colors.head.to_string()),
-            ("Torso", avatar.body_colors.torso.to_string()),
-            ("Right arm", avatar.body_colors.right_arm.to_string()),
-            ("Left arm", avatar.body_colors.left_arm.to_string()),
-            ("Right leg", avatar.body_colors.right_leg.to_string()),
-            ("Left leg", avatar.body_colors.left_leg.to_string()),
+            ("Head", Value::Enum(avatar.body_colors.head.to_string())),
+            ("Torso", Value::Enum(avatar.body_colors.torso.to_string())),
+            ("Right arm", Value::Enum(avatar.body_colors.right_arm.to_string())),
+            ("Left arm", Value::Enum(avatar.body_colors.left_arm.to_string())),
+            ("Right leg", Value::Enum(avatar.body_colors.right_leg.to_string())),
+            ("Left leg", Value::Enum(avatar.body_colors.left_leg.to_string())),
         }),
         ("Assets", assets),
         ("Emotes", emotes));
 
-    print!("{}", object);
+    output::print(&object);
+    Ok(())
 }
 
-pub(crate) async fn outfits(client: &mut Client, id: Option<u64>) {
-    let id = id.unwrap_or(users::v1::authenticated_details(client).await.unwrap().id);
+pub(crate) async fn outfits(client: &mut Client, id: Option<u64>) -> Result<()> {
+    let id = user_id(client, id).await?;
     let result = avatar::v1::user_outfits(client, id, Paging::new(None, Some(100), None), None)
         .await
-        .expect("error: failed to get user outfits");
+        .context("failed to get user outfits")?;
 
     let mut outfits = Vec::new();
     for outfit in &result.outfits {
@@ -230,7 +385,7 @@ pub(crate) async fn outfits(client: &mut Client, id: Option<u64>) {
         outfits.push(Value::from(object!(("Outfit", {
             ("Id", outfit.id),
             ("Name", outfit.name.to_owned()),
-            ("Is editable", outfit.is_editable.to_string()),
+            ("Is editable", outfit.is_editable),
         }))));
     }
 
@@ -240,122 +395,134 @@ pub(crate) async fn outfits(client: &mut Client, id: Option<u64>) {
         ("Outfits", outfits)
     );
 
-    print!("{}", object);
+    output::print(&object);
+    Ok(())
 }
 
-pub(crate) async fn notificatons(client: &mut Client) {
+pub(crate) async fn servers(web: &mut WebClient, id: u64, paging: &PagingArgs) -> Result<()> {
+    paginate(paging, "Servers", 10, 100, async |cursor, limit| {
+        let result =
+            web::games::public_servers(web, id, false, Paging::new(cursor, Some(limit), None))
+                .await
+                .context("failed to get servers")?;
+
+        let entries = result
+            .servers
+            .into_iter()
+            .map(|server| {
+                Value::from(object!(("Server", {
+                    ("Job Id", server.job_id),
+                    ("Players", server.playing),
+                    ("Max players", server.max_players),
+                    ("FPS", server.fps),
+                    ("Ping", server.ping),
+                })))
+            })
+            .collect();
+
+        Ok(Page {
+            entries,
+            next_cursor: result.next_cursor,
+            previous_cursor: result.previous_cursor,
+        })
+    })
+    .await
+}
+
+pub(crate) async fn notificatons(client: &mut Client) -> Result<()> {
     let result = notifications::v2::recent(client, Paging::new(None, Some(20), None))
         .await
-        .expect("error: failed to get notifications");
+        .context("failed to get notifications")?;
 
     let mut notifications = Vec::new();
     for notification in &result {
         let client_events_payload = {
             if &notification.content.notification_type == "ExperienceInvitation" {
                 let payload = &notification.content.client_events_payload;
+                let id = |id: &Option<String>| id.as_deref().and_then(|id| id.parse::<u64>().ok());
+
                 Value::from(object!(
-                    (
-                        "Sender Id",
-                        payload
-                            .sender_user_id
-                            .to_owned()
-                            .unwrap_or("None".to_string())
-                    ),
-                    (
-                        "Universe Id",
-                        payload.universe_id.to_owned().unwrap_or("None".to_string())
-                    ),
-                    (
-                        "Place Id",
-                        payload.place_id.to_owned().unwrap_or("None".to_string())
-                    ),
-                    (
-                        "Root place Id",
-                        payload
-                            .root_place_id
-                            .to_owned()
-                            .unwrap_or("None".to_string())
-                    ),
-                    (
-                        "Trigger",
-                        payload.trigger.to_owned().unwrap_or("None".to_string())
-                    )
+                    ("Sender Id", id(&payload.sender_user_id)),
+                    ("Universe Id", id(&payload.universe_id)),
+                    ("Place Id", id(&payload.place_id)),
+                    ("Root place Id", id(&payload.root_place_id)),
+                    ("Trigger", payload.trigger.to_owned())
                 ))
             } else {
-                Value::from("None")
+                Value::Null
             }
         };
 
         notifications.push(Value::from(object!(("Notification", {
             ("Id", notification.id.to_owned()),
-            ("Event date", notification.event_date.to_string()),
+            ("Event date", &notification.event_date),
             ("Since", notification.timestamp.to_owned()),
-            ("Interacted with", notification.is_interacted.to_string()),
+            ("Interacted with", notification.is_interacted),
             ("Event count", notification.event_count),
             ("Content", {
-                ("Notification type", notification.content.notification_type.to_string()),
-                ("Current state", notification.content.current_state.to_string()),
+                ("Notification type", Value::Enum(notification.content.notification_type.to_owned())),
+                ("Current state", Value::Enum(notification.content.current_state.to_owned())),
                 ("Content", client_events_payload),
             })
         }))));
     }
 
     let object = object!(("Notifications", notifications));
-    print!("{}", object);
+    output::print(&object);
+    Ok(())
 }
 
-pub(crate) async fn conversations(client: &mut Client) {
-    let result = platform_chat::v1::user_conversations(client, Paging::new(None, Some(100), None))
-        .await
-        .expect("error: failed to get user conversations");
-
-    let mut conversations = Vec::new();
-    for conversation in result.conversations {
-        let mut messages = Vec::new();
-        for message in conversation.messages {
-            messages.push(Value::from(object!(("Message", {
-                ("Id", message.id),
-                ("Content", message.content, FieldStyle::Description),
-                ("Kind", message.kind),
-                ("Sent by", message.sender_id),
-                ("Is deleted", message.is_deleted.to_string()),
-                ("Creation date", message.created.to_string()),
+pub(crate) async fn conversations(client: &mut Client, paging: &PagingArgs) -> Result<()> {
+    paginate(paging, "Conversations", 100, 100, async |cursor, limit| {
+        let result =
+            platform_chat::v1::user_conversations(client, Paging::new(cursor, Some(limit), None))
+                .await
+                .context("failed to get user conversations")?;
+
+        let mut conversations = Vec::new();
+        for conversation in result.conversations {
+            let mut messages = Vec::new();
+            for message in conversation.messages {
+                messages.push(Value::from(object!(("Message", {
+                    ("Id", message.id),
+                    ("Content", message.content, FieldStyle::Description),
+                    ("Kind", message.kind),
+                    ("Sent by", message.sender_id),
+                    ("Is deleted", message.is_deleted),
+                    ("Creation date", message.created),
+                }))));
+            }
+
+            conversations.push(Value::from(object!(("Conversation", {
+                ("Id", conversation.id),
+                ("Name", conversation.name),
+                ("Source", conversation.source),
+                ("Creator Id", conversation.creator_id),
+                ("Creation date", conversation.created),
+                ("Last updated", conversation.updated),
+                ("Participants",conversation.participants),
+                ("Unread message count", conversation.unread_message_count),
+                ("Messages", messages)
             }))));
         }
 
-        conversations.push(Value::from(object!(("Conversation", {
-            ("Id", conversation.id.unwrap_or("None".to_string())),
-            ("Name", conversation.name),
-            ("Source", conversation.source),
-            ("Creator Id", conversation.creator_id.unwrap_or(0)),
-            ("Creation date", conversation.created.to_string()),
-            ("Last updated", conversation.updated.to_string()),
-            ("Participants",conversation.participants),
-            ("Unread message count", conversation.unread_message_count),
-            ("Messages", messages)
-        }))));
-    }
-
-    let object = object!(
-        ("Next cursor", result.next_cursor.unwrap_or_default()),
-        (
-            "Previous cursor",
-            result.previous_cursor.unwrap_or_default()
-        ),
-        ("Conversations", conversations)
-    );
-
-    print!("{}", object);
+        Ok(Page {
+            entries: conversations,
+            next_cursor: result.next_cursor,
+            previous_cursor: result.previous_cursor,
+        })
+    })
+    .await
 }
 
-pub(crate) async fn messages(client: &mut Client) {
+pub(crate) async fn messages(client: &mut Client) -> Result<()> {
     let result = private_messages::v1::messages(
         client,
         MessageTab::Inbox,
         Paging::new(Some("0"), Some(100), None),
     )
     .await
-    .expect("error: failed to get user messages");
+    .context("failed to get user messages")?;
 
     let mut messages = Vec::new();
     for message in result.collection {
@@ -366,19 +533,19 @@ pub(crate) async fn messages(client: &mut Client) {
                 ("Id", message.sender.id),
                 ("Name", message.sender.name),
                 ("Display name", message.sender.display_name),
-                ("Is verified", message.sender.is_verified.to_string()),
+                ("Is verified", message.sender.is_verified),
             }),
             ("Recipient", {
                 ("Id", message.recipient.id),
                 ("Name", message.recipient.name),
                 ("Display name", message.recipient.display_name),
-                ("Is verified", message.recipient.is_verified.to_string()),
+                ("Is verified", message.recipient.is_verified),
             }),
-            ("Is read", message.is_read.to_string()),
-            ("Is system message", message.is_system_message.to_string()),
+            ("Is read", message.is_read),
+            ("Is system message", message.is_system_message),
             ("Content", message.body.to_owned(), FieldStyle::Description),
-            ("Creation date", message.created.to_string()),
-            ("Last updated", message.updated.to_string()),
+            ("Creation date", message.created),
+            ("Last updated", message.updated),
         }))));
     }
 
@@ -389,124 +556,209 @@ pub(crate) async fn messages(client: &mut Client) {
         ("Messages", messages),
     );
 
-    print!("{}", object);
+    output::print(&object);
+    Ok(())
 }
 
-pub(crate) async fn followers(client: &mut Client, id: Option<u64>) {
-    let id = id.unwrap_or(users::v1::authenticated_details(client).await.unwrap().id);
-    let result = friends::v1::user_followers(client, id)
-        .await
-        .expect("error: failed to get user followers");
-
-    let followers: Vec<Value> = result
-        .users
-        .into_iter()
-        .map(|user| {
-            Value::from(object!(
-                ("Id", user.id),
-                ("Is verified", user.is_verified.unwrap_or(false).to_string()),
-            ))
-        })
-        .collect();
-
-    let object = object!(
-        ("Next cursor", result.next_cursor.unwrap_or_default()),
-        (
-            "Previous cursor",
-            result.previous_cursor.unwrap_or_default()
-        ),
-        ("Followers", followers)
-    );
+pub(crate) async fn followers(
+    client: &mut Client,
+    web: &mut WebClient,
+    id: Option<u64>,
+    paging: &PagingArgs,
+) -> Result<()> {
+    let id = user_id(client, id).await?;
+
+    paginate(paging, "Followers", 10, 100, async |cursor, limit| {
+        let result = web::friends::user_followers(web, id, Paging::new(cursor, Some(limit), None))
+            .await
+            .context("failed to get user followers")?;
+
+        Ok(user_page(
+            result.users,
+            result.next_cursor,
+            result.previous_cursor,
+        ))
+    })
+    .await
+}
 
-    print!("{}", object);
+pub(crate) async fn followings(
+    client: &mut Client,
+    web: &mut WebClient,
+    id: Option<u64>,
+    paging: &PagingArgs,
+) -> Result<()> {
+    let id = user_id(client, id).await?;
+
+    paginate(paging, "Followings", 10, 100, async |cursor, limit| {
+        let result = web::friends::user_followings(web, id, Paging::new(cursor, Some(limit), None))
+            .await
+            .context("failed to get user followings")?;
+
+        Ok(user_page(
+            result.users,
+            result.next_cursor,
+            result.previous_cursor,
+        ))
+    })
+    .await
 }
 
-pub(crate) async fn followings(client: &mut Client, id: Option<u64>) {
-    let id = id.unwrap_or(users::v1::authenticated_details(client).await.unwrap().id);
-    let result = friends::v1::user_followings(client, id)
-        .await
-        .expect("error: failed to get user followings");
+pub(crate) async fn friends(
+    client: &mut Client,
+    id: Option<u64>,
+    paging: &PagingArgs,
+) -> Result<()> {
+    let id = user_id(client, id).await?;
+
+    paginate(paging, "Friends", 100, 100, async |cursor, limit| {
+        let result =
+            friends::v1::user_friends_find(client, id, Paging::new(cursor, Some(limit), None))
+                .await
+                .context("failed to get user friends")?;
+
+        Ok(user_page(
+            result.users,
+            result.next_cursor,
+            result.previous_cursor,
+        ))
+    })
+    .await
+}
 
-    let followings: Vec<Value> = result
-        .users
+fn user_page(
+    users: Vec<friends::v1::User>,
+    next_cursor: Option<String>,
+    previous_cursor: Option<String>,
+) -> Page {
+    let entries = users
         .into_iter()
         .map(|user| {
             Value::from(object!(
                 ("Id", user.id),
-                ("Is verified", user.is_verified.unwrap_or(false).to_string()),
+                ("Is verified", user.is_verified.unwrap_or(false))
             ))
         })
         .collect();
 
-    let object = object!(
-        ("Next cursor", result.next_cursor.unwrap_or_default()),
-        (
-            "Previous cursor",
-            result.previous_cursor.unwrap_or_default()
-        ),
-        ("Followings", followings)
-    );
-
-    print!("{}", object);
+    Page {
+        entries,
+        next_cursor,
+        previous_cursor,
+    }
 }
 
-pub(crate) async fn friends(client: &mut Client, id: Option<u64>) {
-    let id = id.unwrap_or(users::v1::authenticated_details(client).await.unwrap().id);
-    let result = friends::v1::user_friends_find(client, id, Paging::new(None, Some(100), None))
-        .await
-        .expect("error: failed to get user friends");
+pub(crate) async fn friend_requests(client: &mut Client, paging: &PagingArgs) -> Result<()> {
+    paginate(
+        paging,
+        "Friend requests",
+        100,
+        100,
+        async |cursor, limit| {
+            let result =
+                friends::v1::friend_requests(client, Paging::new(cursor, Some(limit), None))
+                    .await
+                    .context("failed to get friend requests")?;
+
+            let mut friend_requests = Vec::new();
+            for request in result.requests {
+                friend_requests.push(Value::from(object!(("Friend request", {
+                    ("Requestor", {
+                        ("Id", request.requester.id),
+                        ("Display name", request.requester.display_name.to_owned()),
+                        ("Contact name", request.requester.contact_name),
+                        ("Universe Id", request.requester.source_universe_id),
+                        //("Origin source", request.requester.origin_source_type.to_string()),
+                        ("Sent at", request.requester.sent_at),
+                    }),
+                    ("Mutual friends", request.mutual_friends_list),
+                }))));
+            }
 
-    let friends: Vec<Value> = result
-        .users
-        .into_iter()
-        .map(|user| {
-            Value::from(object!(
-                ("Id", user.id),
-                ("Is verified", user.is_verified.unwrap_or(false).to_string())
-            ))
-        })
-        .collect();
+            Ok(Page {
+                entries: friend_requests,
+                next_cursor: result.next_cursor,
+                previous_cursor: result.previous_cursor,
+            })
+        },
+    )
+    .await
+}
 
-    let object = object!(
-        ("Next cursor", result.next_cursor.unwrap_or_default()),
-        (
-            "Previous cursor",
-            result.previous_cursor.unwrap_or_default()
-        ),
-        ("Friends", friends)
-    );
+pub(crate) async fn trades(
+    web: &mut WebClient,
+    kind: TradeStatusType,
+    verbose: bool,
+    paging: &PagingArgs,
+) -> Result<()> {
+    paginate(paging, "Trades", 10, 100, async |cursor, limit| {
+        let result = web::trades::trades(web, kind, Paging::new(cursor, Some(limit), None))
+            .await
+            .context("failed to get trades")?;
+
+        let mut trades = Vec::new();
+        for trade in result.trades {
+            let mut builder = ObjectBuilder::default()
+                .with_field(Field::new("Id", Value::from(trade.id)))
+                .with_field(Field::new(
+                    "Partner",
+                    Value::from(object!(
+                        ("Id", trade.user.id),
+                        ("Name", trade.user.name),
+                        ("Display name", trade.user.display_name),
+                    )),
+                ))
+                .with_field(Field::new("Status", Value::Enum(trade.status)))
+                .with_field(Field::new("Is active", Value::from(trade.is_active)))
+                .with_field(Field::new("Creation date", Value::from(&trade.created)))
+                .with_field(Field::new("Expiration date", Value::from(trade.expiration)));
 
-    print!("{}", object);
-}
+            if verbose {
+                let details = web::trades::trade_details(web, trade.id)
+                    .await
+                    .context("failed to get trade details")?;
+
+                // The offer of the partner is what is being requested from them
+                for offer in details.offers {
+                    let key = if offer.user.id == trade.user.id {
+                        "Requested"
+                    } else {
+                        "Offered"
+                    };
+
+                    let items: Vec<Value> = offer
+                        .user_assets
+                        .into_iter()
+                        .map(|asset| {
+                            Value::from(object!(("Item", {
+                                ("Id", asset.user_asset_id),
+                                ("Asset Id", asset.asset_id),
+                                ("Name", asset.name),
+                                ("Serial number", asset.serial_number),
+                                ("Recent average price", asset.recent_average_price, FieldStyle::Price),
+                                ("Original price", asset.original_price, FieldStyle::Price),
+                            })))
+                        })
+                        .collect();
 
-pub(crate) async fn friend_requests(client: &mut Client) {
-    let result = friends::v1::friend_requests(client, Paging::new(None, Some(100), None))
-        .await
-        .expect("error: failed to get friend requests");
-
-    let mut friend_requests = Vec::new();
-    for request in result.requests {
-        friend_requests.push(Value::from(object!(("Friend request", {
-            ("Requestor", {
-                ("Id", request.requester.id),
-                ("Display name", request.requester.display_name.to_owned()),
-                ("Contact name", request.requester.contact_name.unwrap_or("None".to_string()).to_owned()),
-                ("Universe Id", request.requester.source_universe_id.unwrap_or(0)),
-                //("Origin source", request.requester.origin_source_type.to_string()),
-                ("Sent at", request.requester.sent_at.to_string()),
-            }),
-            ("Mutual friends", request.mutual_friends_list),
-        }))));
-    }
+                    builder = builder.with_field(Field::new(
+                        key,
+                        Value::from(object!(
+                            ("Robux", offer.robux, FieldStyle::Price),
+                            ("Items", items)
+                        )),
+                    ));
+                }
+            }
 
-    let object = object!(
-        ("Next cursor", result.next_cursor.unwrap_or_default()),
-        (
-            "Previous cursor",
-            result.previous_cursor.unwrap_or_default()
-        ),
-        ("Friend requests", friend_requests)
-    );
+            trades.push(Value::from(object!(("Trade", builder.build()))));
+        }
 
-    print!("{}", object);
+        Ok(Page {
+            entries: trades,
+            next_cursor: result.next_cursor,
+            previous_cursor: result.previous_cursor,
+        })
+    })
+    .await
 }
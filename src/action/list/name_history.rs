@@ -1,60 +1,51 @@
-use roblox_api::{
-    Paging,
-    api::{
-        groups,
-        users::{self, v1::user_username_history},
-    },
-    client::Client,
-};
+use roblox_api::{Paging, api::users::v1::user_username_history, client::Client};
 
+use crate::action::user_id;
+use crate::command::list::PagingArgs;
+use crate::error::{Context, Result};
 use crate::object;
 use crate::object::Value;
+use crate::web::{self, WebClient};
 
-pub(crate) async fn user(client: &mut Client, id: Option<u64>) {
-    let id = id.unwrap_or(users::v1::authenticated_details(client).await.unwrap().id);
+use super::{Page, paginate};
 
-    let result = user_username_history(client, id, Paging::new(None, Some(100), None))
-        .await
-        .expect("error: failed to get user's name history");
+pub(crate) async fn user(client: &mut Client, id: Option<u64>, paging: &PagingArgs) -> Result<()> {
+    let id = user_id(client, id).await?;
 
-    let object = object!(
-        (
-            "Next cursor",
-            result.next_cursor.unwrap_or_default().to_owned()
-        ),
-        (
-            "Previous cursor",
-            result.previous_cursor.unwrap_or_default().to_owned()
-        ),
-        ("Names", result.names),
-    );
+    paginate(paging, "Names", 100, 100, async |cursor, limit| {
+        let result = user_username_history(client, id, Paging::new(cursor, Some(limit), None))
+            .await
+            .context("failed to get user's name history")?;
 
-    print!("{}", object);
+        Ok(Page {
+            entries: result.names.into_iter().map(Value::from).collect(),
+            next_cursor: result.next_cursor,
+            previous_cursor: result.previous_cursor,
+        })
+    })
+    .await
 }
 
-pub(crate) async fn group(client: &mut Client, id: u64) {
-    let result = groups::v1::name_history(client, id)
-        .await
-        .expect("error: failed to get group name history");
-
-    let (names, dates): (Vec<Value>, Vec<Value>) = result
-        .names
-        .into_iter()
-        .map(|(x, y)| (Value::from(x), Value::from(y.to_string())))
-        .collect();
-
-    let object = object!(
-        (
-            "Next cursor",
-            result.next_cursor.unwrap_or_default().to_owned()
-        ),
-        (
-            "Previous cursor",
-            result.previous_cursor.unwrap_or_default().to_owned()
-        ),
-        ("Names", names),
-        ("Dates", dates)
-    );
-
-    print!("{}", object);
+pub(crate) async fn group(web: &mut WebClient, id: u64, paging: &PagingArgs) -> Result<()> {
+    paginate(paging, "Names", 10, 100, async |cursor, limit| {
+        let result = web::groups::name_history(web, id, Paging::new(cursor, Some(limit), None))
+            .await
+            .context("failed to get group name history")?;
+
+        Ok(Page {
+            entries: result
+                .names
+                .into_iter()
+                .map(|name| {
+                    Value::from(object!(
+                        ("Name", name.name),
+                        ("Creation date", &name.created)
+                    ))
+                })
+                .collect(),
+            next_cursor: result.next_cursor,
+            previous_cursor: result.previous_cursor,
+        })
+    })
+    .await
 }
@@ -1,44 +1,138 @@
-use std::io::Write;
+use std::{io::Write, time::Duration};
 
-use roblox_api::{api::auth_token_service, client::Client};
+use chrono::{DateTime, Utc};
+use console::{Term, style};
+use roblox_api::{
+    api::auth_token_service::{self, v1::LoginStatus},
+    client::{Client, Cookie},
+};
 
-use crate::{config::Account, object};
+use super::{account, challenge};
+use crate::{
+    config::{Account, Config},
+    error::{Context, Error, Result},
+    object,
+    object::{Field, ObjectBuilder, Value},
+    output::{self, Format},
+    qr::QrCode,
+    web::{self, WebClient},
+};
 
-pub(crate) async fn quick_login(client: &mut Client, account: &Account) {
-    // TODO: print authenticated account and prompt first
+/// What the quick login QR code links to, the page a logged in phone confirms the code on
+const CONFIRM_URL: &str = "https://www.roblox.com/crossdevicelogin/ConfirmCode";
 
-    if !prompt(&format!("Create login for {}?", account.name)) {
-        println!("warn: login creation cancelled");
-        return;
-    };
+/// How often the status of a quick login is checked while waiting for it to be validated
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
 
-    let token = auth_token_service::v1::login_create(client)
+/// Creates a quick login code, waits for it to be entered on a logged in device and stores the
+/// session it creates as the account `name`
+pub(crate) async fn quick_login(cfg: Config, name: &str) -> Result<()> {
+    if cfg.position(name).is_some() {
+        return Err(Error::Config(format!(
+            "account with username: {name} already exists"
+        )));
+    }
+
+    let mut client = Client::from_cookie(Cookie::default());
+    let token = auth_token_service::v1::login_create(&mut client)
         .await
-        .expect("error: failed to create authentication login ticket");
+        .context("failed to create authentication login ticket")?;
 
-    let object = object!(
-        ("Token", {
-            ("Code", token.code.clone()),
-            ("Status", token.status),
-            ("Private key", token.private_key.clone()),
-            ("Expiration time", token.expiration_time.to_string()),
-            ("QR code image url", format!(
+    let qr_code = qr_code(&token.code, &token.private_key);
+
+    let mut fields = ObjectBuilder::default()
+        .with_field(Field::new("Code", Value::from(token.code.clone())))
+        .with_field(Field::new("Status", Value::from(token.status)))
+        .with_field(Field::new(
+            "Private key",
+            Value::from(token.private_key.clone()),
+        ))
+        .with_field(Field::new(
+            "Expiration time",
+            Value::from(&token.expiration_time),
+        ));
+
+    // Without a terminal to draw the QR code in, it has to be opened in a browser
+    if qr_code.is_none() {
+        fields = fields.with_field(Field::new(
+            "QR code image url",
+            Value::from(format!(
                 "{}/login/qr-code-image?key={}&code={}",
                 auth_token_service::v1::URL,
                 token.private_key,
-                token.code)
-            ),
-        }),
+                token.code
+            )),
+        ));
+    }
+
+    output::print(&object!(("Token", fields.build())));
+
+    if let Some(qr_code) = qr_code {
+        for line in qr_code.render() {
+            println!("{}", style(line).white().on_black());
+        }
+    }
+
+    let expiration = DateTime::parse_from_rfc3339(&token.expiration_time.to_string())
+        .map_err(|_| Error::Api("unexpected login expiration time".to_string()))?;
+
+    eprintln!(
+        "info: waiting for the code to be entered on a logged in device, or with `login authorize {}`",
+        token.code
+    );
+
+    loop {
+        if Utc::now() >= expiration {
+            return Err(Error::Auth(
+                "login code expired before it was validated".to_string(),
+            ));
+        }
+
+        tokio::time::sleep(POLL_INTERVAL).await;
+
+        let status =
+            auth_token_service::v1::login_status(&mut client, &token.code, &token.private_key)
+                .await
+                .context("failed to get login status")?;
+
+        if status.status == LoginStatus::Validated {
+            if let Some(account_name) = status.account_name {
+                eprintln!("info: login validated by {account_name}");
+            }
+
+            break;
+        }
+    }
+
+    let mut web = WebClient::anonymous();
+    let (user, cookie) = challenge::retry(&mut client, &mut web, None, async |_, web| {
+        web::auth::login_with_token(web, &token.code, &token.private_key).await
+    })
+    .await
+    .context("failed to complete login")?;
+
+    eprintln!(
+        "info: logged in as {} ({}), id: {}",
+        user.display_name, user.name, user.id
     );
 
-    print!("{}", object);
+    account::add(cfg, name, &cookie.value, cookie.expires).await
+}
+
+/// The QR code for a quick login, only when it can be drawn in a terminal
+fn qr_code(code: &str, private_key: &str) -> Option<QrCode> {
+    if !Term::stdout().is_term() || output::options().format != Format::Pretty {
+        return None;
+    }
+
+    QrCode::encode(format!("{CONFIRM_URL}?code={code}&key={private_key}").as_bytes())
 }
 
-pub(crate) async fn authorize_login(client: &mut Client, code: &str) {
+pub(crate) async fn authorize_login(client: &mut Client, code: &str) -> Result<()> {
     let _ = auth_token_service::v1::inspect_code(client, code).await;
     let info = auth_token_service::v1::inspect_code(client, code)
         .await
-        .expect("error: failed to inspect authentication code");
+        .context("failed to inspect authentication code")?;
 
     let object = object!(
         ("Info", {
@@ -47,30 +141,146 @@ pub(crate) async fn authorize_login(client: &mut Client, code: &str) {
         })
     );
 
-    print!("{}", object);
+    output::print(&object);
 
-    if !prompt("Verify login?") {
+    if !prompt("Verify login?")? {
         println!("warn: login verification cancelled");
-        return;
+        return Ok(());
     }
 
     auth_token_service::v1::validate_code(client, code)
         .await
-        .expect("error: failed to validate authentication code");
+        .context("failed to validate authentication code")?;
     println!("info: validating code");
+    Ok(())
+}
+
+/// Invalidates the session of the cookie in use, removing the account it belongs to from the list
+pub(crate) async fn logout(
+    client: &mut Client,
+    web: &mut WebClient,
+    cfg: &Config,
+    account: Option<&Account>,
+) -> Result<()> {
+    challenge::retry(client, web, account, async |_, web| {
+        web::auth::logout(web).await
+    })
+    .await
+    .context("failed to log out")?;
+
+    match account {
+        Some(account) => account::remove(cfg.clone(), &account.name),
+        None => {
+            println!("info: logged out");
+            Ok(())
+        }
+    }
+}
+
+pub(crate) async fn sessions(web: &mut WebClient) -> Result<()> {
+    let mut sessions = Vec::new();
+    let mut cursor = None;
+
+    loop {
+        let page = web::sessions::sessions(web, cursor.as_deref())
+            .await
+            .context("failed to get sessions")?;
+
+        sessions.extend(page.sessions);
+        cursor = page.next_cursor.filter(|cursor| !cursor.is_empty());
+        if !page.has_more || cursor.is_none() {
+            break;
+        }
+    }
+
+    let sessions: Vec<Value> = sessions
+        .into_iter()
+        .map(|session| {
+            let device = [session.agent.value, session.agent.os]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            let location = [
+                session.location.city,
+                session.location.subdivision,
+                session.location.country,
+            ]
+            .into_iter()
+            .flatten()
+            .filter(|x| !x.is_empty())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+            // Sent as a string, though older responses had it as a number
+            let last_used = session
+                .last_accessed
+                .and_then(|x| match x {
+                    serde_json::Value::String(x) => x.parse::<i64>().ok(),
+                    x => x.as_i64(),
+                })
+                .and_then(DateTime::from_timestamp_millis)
+                .map(|x| Value::Timestamp(x.fixed_offset()));
+
+            Value::from(object!(("Session", {
+                ("Device", Some(device).filter(|x| !x.is_empty())),
+                ("Location", Some(location).filter(|x| !x.is_empty())),
+                ("IP", session.last_accessed_ip),
+                ("Last used", last_used),
+                ("Current", session.is_current_session),
+            })))
+        })
+        .collect();
+
+    output::print(&object!(("Sessions", sessions)));
+    Ok(())
+}
+
+/// Signs out every session but a new one which replaces the cookie in use, stored back into the
+/// account it belongs to
+pub(crate) async fn revoke_others(
+    client: &mut Client,
+    web: &mut WebClient,
+    cfg: &Config,
+    account: Option<&Account>,
+) -> Result<()> {
+    let cookie = challenge::retry(client, web, account, async |_, web| {
+        web::auth::logout_from_all_sessions(web).await
+    })
+    .await
+    .context("failed to sign out other sessions")?;
+
+    match account {
+        Some(account) => {
+            account::replace_cookie(cfg.clone(), &account.name, &cookie)?;
+            println!(
+                "info: signed out every other session of account: {}",
+                account.name
+            );
+        }
+
+        // The old cookie no longer works, so the new one has to go somewhere
+        None => {
+            eprintln!("info: signed out every other session, the cookie in use was replaced by:");
+            println!("{}", cookie.value);
+        }
+    }
+
+    Ok(())
 }
 
-fn prompt(title: &str) -> bool {
+fn prompt(title: &str) -> Result<bool> {
     loop {
         print!("{title} [y/N] ");
         std::io::stdout()
             .flush()
-            .expect("error: failed to flush to stdout");
+            .context("failed to flush to stdout")?;
 
         let mut prompt = String::new();
         std::io::stdin()
             .read_line(&mut prompt)
-            .expect("error: failed to read stdin");
+            .context("failed to read stdin")?;
 
         match prompt
             .to_lowercase()
@@ -79,11 +289,11 @@ fn prompt(title: &str) -> bool {
             .as_str()
         {
             "y" => {
-                return true;
+                return Ok(true);
             }
 
             "n" | "" => {
-                return false;
+                return Ok(false);
             }
 
             _ => {
@@ -1,6 +1,23 @@
+use roblox_api::{api::users, client::Client};
+
+use crate::error::{Context, Result};
+
+pub(crate) mod account;
+pub(crate) mod challenge;
 pub(crate) mod download;
 pub(crate) mod info;
 pub(crate) mod join;
 pub(crate) mod list;
 pub(crate) mod login;
 pub(crate) mod status;
+
+/// `id`, or the Id of the authenticated account when none was given
+pub(crate) async fn user_id(client: &mut Client, id: Option<u64>) -> Result<u64> {
+    match id {
+        Some(id) => Ok(id),
+        None => Ok(users::v1::authenticated_details(client)
+            .await
+            .context("failed to get authenticated account")?
+            .id),
+    }
+}
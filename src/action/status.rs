@@ -1,80 +1,145 @@
 use roblox_api::{
+    ApiError,
     api::{economy, premium_features, presence, users},
     client::Client,
 };
 
-use crate::{Config, object, object::FieldStyle};
+use crate::{
+    Config,
+    config::Account,
+    error::{self, Context, Error, Result},
+    object,
+    object::{FieldStyle, Object, Value},
+    output, secret,
+    web::{self, WebClient},
+};
+
+/// Whether the cookie of an account can still be used
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    Valid,
+    Expired,
+    Locked,
+    Banned,
+}
+
+impl State {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Valid => "valid",
+            Self::Expired => "expired",
+            Self::Locked => "locked",
+            Self::Banned => "banned",
+        }
+    }
+}
+
+/// Why the cookie of `account` was rejected, a failure which has nothing to do with the account
+/// is returned as is
+async fn invalid_state(cookie: &str, error: roblox_api::Error) -> Result<(State, Option<String>)> {
+    // Banned accounts can still look at their punishment, whatever else they were told
+    let mut web = WebClient::from_cookie(cookie);
+    if let Ok(Some(punishment)) = web::moderation::not_approved(&mut web).await {
+        let reason = match punishment.end_date {
+            Some(end_date) => format!(
+                "{} until {end_date}",
+                punishment.punishment_type_description.unwrap_or_default()
+            ),
+            None => punishment.punishment_type_description.unwrap_or_default(),
+        };
+
+        return Ok((State::Banned, Some(reason)));
+    }
+
+    let locked = matches!(
+        error,
+        roblox_api::Error::ApiError(
+            ApiError::AccontLocked | ApiError::AccountIssue | ApiError::PinIsLocked
+        )
+    );
+
+    match Error::from(error) {
+        Error::Auth(message) if locked => Ok((State::Locked, Some(message))),
+        Error::Auth(message) => Ok((State::Expired, Some(message))),
+        error => Err(error),
+    }
+}
+
+async fn fetch(account: Account, cookie: String) -> Result<Object> {
+    let context = format!("failed to get status of account {}", account.name);
+    let mut client = Client::from_cookie(cookie.as_str().into());
+
+    let details = match users::v1::authenticated_details(&mut client).await {
+        Ok(details) => details,
+        Err(error) => {
+            let (state, reason) = invalid_state(&cookie, error).await.context(&context)?;
+            return Ok(object!(("Account", {
+                ("Id", account.id),
+                ("Aliased name", account.name.to_owned()),
+                ("Username", account.username.to_owned()),
+                ("State", Value::Enum(state.name().to_string())),
+                ("Reason", reason),
+            })));
+        }
+    };
+
+    // TODO: could save a few milliseconds by doing the api calls separately
+    let currency = economy::v1::currency(&mut client).await.context(&context)?;
+    let is_premium = premium_features::v1::is_premium(&mut client, details.id)
+        .await
+        .context(&context)?;
 
-// TODO: move tokio::spawn nest in here
-//async fn fetch_account_status() ->  {}
+    // TODO: move outside of loop, presence api supports 50 users at once
+    let presences = presence::v1::presence(&mut client, &[details.id])
+        .await
+        .context(&context)?;
 
-pub(crate) async fn print(cfg: &Config) {
-    println!("fetching account info..");
+    let gender = users::v1::gender(&mut client).await.context(&context)?;
+    let country_code = users::v1::authenticated_country_code(&mut client)
+        .await
+        .context(&context)?;
+    let info = users::v1::user_details(&mut client, details.id)
+        .await
+        .context(&context)?;
+
+    let presence = match presences.first() {
+        Some(presence) => Value::from(presence.status.to_owned()),
+        None => Value::Null,
+    };
+
+    Ok(object!(("Account", {
+        ("Id", details.id),
+        ("Aliased name", account.name.to_owned()),
+        ("Display name", details.display_name.to_owned()),
+        ("State", Value::Enum(State::Valid.name().to_string())),
+        ("Gender", Value::Enum(gender.to_string())),
+        ("Creation date", &info.created),
+        ("Premium", is_premium),
+        ("Robux", currency, FieldStyle::Price),
+        ("Country", Value::Enum(country_code.to_owned())),
+        ("Presence", presence)
+    })))
+}
+
+pub(crate) async fn print(cfg: &Config) -> Result<()> {
+    eprintln!("fetching account info..");
 
     let mut handles = Vec::new();
     for account in &cfg.accounts {
         let account = account.clone();
+        let cookie = secret::load(&account);
 
-        // TODO: could save a few milliseconds by doing the api calls separately
         handles.push(tokio::spawn(async move {
-            let mut client = Client::from_cookie(account.cookie.as_str().into());
-
-            let details = users::v1::authenticated_details(&mut client).await.unwrap();
-            let currency = economy::v1::currency(&mut client).await.unwrap();
-            let is_premium = premium_features::v1::is_premium(&mut client, details.id)
-                .await
-                .unwrap();
-
-            // TODO: move outside of loop, presence api supports 50 users at once
-            let presences = presence::v1::presence(&mut client, &[details.id])
-                .await
-                .unwrap();
-
-            let gender = users::v1::gender(&mut client).await.unwrap();
-            let country_code = users::v1::authenticated_country_code(&mut client)
-                .await
-                .unwrap();
-            let info = users::v1::user_details(&mut client, details.id)
-                .await
-                .unwrap();
-
-            (
-                account,
-                info,
-                details,
-                currency,
-                is_premium,
-                presences,
-                gender,
-                country_code,
-            )
+            let context = format!("failed to get status of account {}", account.name);
+            let cookie = cookie.context(&context)?;
+            fetch(account, cookie).await
         }));
     }
 
     let results = futures::future::join_all(handles).await;
-    for result in &results {
-        match result {
-            Ok((account, info, details, currency, is_premium, presences, gender, country_code)) => {
-                let presence = presences.first().unwrap();
-
-                let object = object!(("Account", {
-                    ("Id", details.id),
-                    ("Aliased name", account.name.to_owned()),
-                    ("Display name",details.display_name.to_owned()),
-                    ("Gender", gender.to_string(), FieldStyle::Enum),
-                    ("Creation date", info.created.to_string()),
-                    ("Premium", *is_premium),
-                    ("Robux", currency.to_owned(), FieldStyle::Price),
-                    ("Country", country_code.to_owned(), FieldStyle::Enum),
-                    ("Presence", presence.status.to_owned())
-                }));
-
-                print!("{}", object);
-            }
-
-            Err(error) => {
-                eprintln!("{}", error)
-            }
-        }
-    }
+    error::report_all(results.into_iter().map(|result| {
+        let object = result.map_err(|error| Error::Api(error.to_string()))??;
+        output::print(&object);
+        Ok(())
+    }))
 }
@@ -0,0 +1,133 @@
+use std::path::{Path, PathBuf};
+
+use openssl::{
+    hash::MessageDigest,
+    pkcs5,
+    symm::{self, Cipher},
+};
+
+use super::{COOKIE_NAME, decode_hex, home, not_found, query};
+use crate::{
+    error::{Error, Result},
+    secret::keyring,
+};
+
+const SALT: &[u8] = b"saltysalt";
+const IV: [u8; 16] = [b' '; 16];
+
+/// The password of `v10` cookies, used when chromium has no keyring to keep a real one in
+const PEANUTS: &str = "peanuts";
+
+/// Since this database version values are prefixed with the SHA-256 of their domain
+const DOMAIN_HASH_VERSION: u32 = 24;
+
+fn database(profile: Option<&Path>) -> Result<PathBuf> {
+    let profile = match profile {
+        Some(profile) => profile.to_owned(),
+        None => home()?.join(".config/chromium/Default"),
+    };
+
+    // Newer versions moved the database into the network service directory
+    let network = profile.join("Network/Cookies");
+    if network.exists() {
+        return Ok(network);
+    }
+
+    Ok(profile.join("Cookies"))
+}
+
+/// The password of `v11` cookies, chrome keeps it under its own application name
+fn keyring_password() -> Result<String> {
+    for application in ["chromium", "chrome"] {
+        if let Some(password) = keyring::find(&["application", application])? {
+            return Ok(password);
+        }
+    }
+
+    Err(Error::NotFound(
+        "the chromium cookie password is not in the keyring".to_string(),
+    ))
+}
+
+fn decrypt(value: &[u8], version: u32) -> Result<String> {
+    let (password, data) = match value.split_at_checked(3) {
+        Some((b"v10", data)) => (PEANUTS.to_string(), data),
+        Some((b"v11", data)) => (keyring_password()?, data),
+        _ => {
+            return Err(Error::Config(
+                "the chromium cookie is encrypted in an unknown way".to_string(),
+            ));
+        }
+    };
+
+    let mut key = [0; 16];
+    pkcs5::pbkdf2_hmac(
+        password.as_bytes(),
+        SALT,
+        1,
+        MessageDigest::sha1(),
+        &mut key,
+    )
+    .map_err(|error| Error::Io(format!("failed to derive the chromium key: {error}")))?;
+
+    let plaintext = symm::decrypt(Cipher::aes_128_cbc(), &key, Some(&IV), data)
+        .map_err(|_| Error::Auth("failed to decrypt the chromium cookie".to_string()))?;
+
+    let plaintext = match version >= DOMAIN_HASH_VERSION {
+        true => plaintext.get(32..).unwrap_or_default(),
+        false => &plaintext,
+    };
+
+    String::from_utf8(plaintext.to_vec())
+        .map_err(|_| Error::Auth("failed to decrypt the chromium cookie".to_string()))
+}
+
+/// Chromium on linux encrypts cookies with AES-128-CBC, keyed by a password from the keyring
+pub(super) fn cookie(profile: Option<&Path>) -> Result<String> {
+    let rows = query(
+        &database(profile)?,
+        &format!(
+            "SELECT hex(value), hex(encrypted_value), \
+            (SELECT value FROM meta WHERE key = 'version') FROM cookies \
+            WHERE host_key LIKE '%roblox.com' AND name = '{COOKIE_NAME}' \
+            ORDER BY last_access_utc DESC LIMIT 1"
+        ),
+    )?;
+
+    let [value, encrypted_value, version] = rows.first().map(Vec::as_slice).unwrap_or_default()
+    else {
+        return Err(not_found("chromium"));
+    };
+
+    // Values are only left unencrypted when chromium couldn't encrypt them at all
+    if !value.is_empty() {
+        return String::from_utf8(decode_hex(value)?)
+            .map_err(|_| Error::Config("the chromium cookie isn't valid text".to_string()));
+    }
+
+    decrypt(
+        &decode_hex(encrypted_value)?,
+        version.parse().unwrap_or_default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::decrypt;
+    use crate::browser::decode_hex;
+
+    #[test]
+    fn decrypt_peanuts() {
+        let value = decode_hex(concat!(
+            "763130",
+            "359539c1cd7e840589be3184ffa17ebdd8dda6011cdec71b8f88a929c4e5e2d1",
+            "3b7a15d7c04de2cd4babdf22ad531393"
+        ))
+        .unwrap();
+
+        assert_eq!(
+            decrypt(&value, 20).unwrap(),
+            "_|WARNING:-DO-NOT-SHARE-THIS.--test"
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use std::path::{Path, PathBuf};
+
+use super::{COOKIE_NAME, decode_hex, home, not_found, query};
+use crate::error::{Context, Error, Result};
+
+const DATABASE: &str = "cookies.sqlite";
+
+/// The profile with the most recently written cookies, firefox doesn't mark the one in use
+fn default_database() -> Result<PathBuf> {
+    home()?
+        .join(".mozilla/firefox")
+        .read_dir()
+        .context("failed to read the firefox profiles")?
+        .flatten()
+        .map(|entry| entry.path().join(DATABASE))
+        .filter_map(|path| Some((path.metadata().ok()?.modified().ok()?, path)))
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+        .ok_or(Error::NotFound("no firefox profile found".to_string()))
+}
+
+/// Firefox stores cookies as plain text
+pub(super) fn cookie(profile: Option<&Path>) -> Result<String> {
+    let database = match profile {
+        Some(profile) => profile.join(DATABASE),
+        None => default_database()?,
+    };
+
+    let rows = query(
+        &database,
+        &format!(
+            "SELECT hex(value) FROM moz_cookies \
+            WHERE host LIKE '%roblox.com' AND name = '{COOKIE_NAME}' \
+            ORDER BY lastAccessed DESC LIMIT 1"
+        ),
+    )?;
+
+    let value = rows
+        .first()
+        .and_then(|row| row.first())
+        .ok_or(not_found("firefox"))?;
+
+    String::from_utf8(decode_hex(value)?)
+        .map_err(|_| Error::Config("the firefox cookie isn't valid text".to_string()))
+}
@@ -0,0 +1,153 @@
+mod chromium;
+mod firefox;
+
+use std::{
+    env,
+    fs::{self, DirBuilder, File},
+    io,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use clap::ValueEnum;
+
+use crate::error::{Context, Error, Result};
+
+/// The cookie roblox authenticates with
+const COOKIE_NAME: &str = ".ROBLOSECURITY";
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub(crate) enum Browser {
+    Firefox,
+    Chromium,
+}
+
+/// Reads the roblox cookie out of the cookie database of `browser`, `profile` is the profile
+/// directory and defaults to the one the browser uses
+pub(crate) fn cookie(browser: Browser, profile: Option<&Path>) -> Result<String> {
+    match browser {
+        Browser::Firefox => firefox::cookie(profile),
+        Browser::Chromium => chromium::cookie(profile),
+    }
+}
+
+fn home() -> Result<PathBuf> {
+    dirs::home_dir().ok_or(Error::Config("home directory not found".to_string()))
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+/// Runs `query` with `sqlite3` on a copy of `database`, since the browser keeps it locked while
+/// running. Columns are split on `|`, so they have to be free of it, like the output of `hex()`
+fn query(database: &Path, query: &str) -> Result<Vec<Vec<String>>> {
+    if !database.exists() {
+        return Err(Error::NotFound(format!(
+            "cookie database not found at {}",
+            database.display()
+        )));
+    }
+
+    // The copy holds the cookies of every site, so only this user may look into its directory
+    let directory = private_directory()?;
+    let copy = directory.join("cookies.sqlite");
+
+    // The output mode is given in full so a `.sqliterc` can't change what gets split
+    let output = copy_database(database, &copy).and_then(|()| {
+        Command::new("sqlite3")
+            .args(["-batch", "-list", "-noheader", "-separator", "|"])
+            .arg(&copy)
+            .arg(query)
+            .output()
+            .map_err(|error| match error.kind() {
+                io::ErrorKind::NotFound => Error::Config(
+                    "sqlite3 is needed to read the cookie database, install it first".to_string(),
+                ),
+                _ => Error::from(error),
+            })
+            .context("failed to run sqlite3")
+    });
+
+    let _ = fs::remove_dir_all(&directory);
+
+    let output = output?;
+    if !output.status.success() {
+        return Err(Error::Io(format!(
+            "failed to read the cookie database: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| line.split('|').map(str::to_owned).collect())
+        .collect())
+}
+
+/// A new directory in the temp directory with a random name, only accessible by this user
+fn private_directory() -> Result<PathBuf> {
+    let mut bytes = [0; 16];
+    openssl::rand::rand_bytes(&mut bytes)
+        .map_err(|error| Error::Io(format!("failed to generate a directory name: {error}")))?;
+
+    let name: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+    let directory = env::temp_dir().join(format!("{}-{name}", env!("CARGO_BIN_NAME")));
+
+    // Fails when anything, like a planted symlink, is already there
+    let mut builder = DirBuilder::new();
+
+    #[cfg(target_family = "unix")]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+
+    builder
+        .create(&directory)
+        .context("failed to create a directory for the cookie database")?;
+
+    Ok(directory)
+}
+
+/// Copies `database` and its write-ahead log, which holds the recent writes, to `copy`
+fn copy_database(database: &Path, copy: &Path) -> Result<()> {
+    let mut copies = vec![(database.to_owned(), copy.to_owned())];
+    let wal = with_suffix(database, "-wal");
+    if wal.exists() {
+        copies.push((wal, with_suffix(copy, "-wal")));
+    }
+
+    for (from, to) in &copies {
+        let mut from = File::open(from).context("failed to open the cookie database")?;
+        let mut options = File::options();
+        options.write(true).create_new(true);
+
+        #[cfg(target_family = "unix")]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let mut to = options
+            .open(to)
+            .context("failed to copy the cookie database")?;
+
+        io::copy(&mut from, &mut to).context("failed to copy the cookie database")?;
+    }
+
+    Ok(())
+}
+
+fn decode_hex(hex: &str) -> Result<Vec<u8>> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|index| {
+            hex.get(index..index + 2)
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                .ok_or(Error::Io("unexpected output from sqlite3".to_string()))
+        })
+        .collect()
+}
+
+fn not_found(browser: &str) -> Error {
+    Error::NotFound(format!(
+        "no roblox cookie found in the {browser} profile, log into roblox with it first"
+    ))
+}
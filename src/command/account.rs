@@ -0,0 +1,51 @@
+use clap::{Args, Subcommand};
+
+use crate::secret::Backend;
+
+#[derive(Debug, Args)]
+pub(crate) struct AccountCommand {
+    #[command(subcommand)]
+    pub(crate) command: AccountCommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum AccountCommands {
+    /// List the accounts in the config, the first one is the default
+    List,
+    /// Remove an account from the config
+    Remove { name: String },
+    /// Change the name an account is aliased by
+    Rename { name: String, new_name: String },
+    /// Make an account the one used when `--account` isn't given
+    SetDefault { name: String },
+    /// Swap cookies close to expiry for new sessions, logging the old ones out
+    Refresh {
+        /// Refresh the cookies which expire within this many days
+        #[arg(long, value_name = "DAYS", default_value_t = 7)]
+        within: u32,
+        /// Refresh every cookie, including the ones whose expiry isn't known, like browser imports
+        #[arg(long)]
+        all: bool,
+    },
+    /// Move every cookie into another backend, new accounts are stored there too
+    Migrate { backend: Backend },
+    /// Store the authenticator secret two step verification codes are generated from
+    SetTotp {
+        name: String,
+        /// `-` reads the base32 secret behind the authenticator QR code from stdin, it's asked for
+        /// when not given
+        #[arg(value_name = "-", value_parser = stdin_only)]
+        secret: Option<String>,
+        /// Remove the stored secret instead, codes are asked for again
+        #[arg(long, conflicts_with = "secret")]
+        clear: bool,
+    },
+}
+
+/// Keeps the secret out of the process list and shell history, where a literal one would end up
+fn stdin_only(value: &str) -> Result<String, String> {
+    match value {
+        "-" => Ok(value.to_owned()),
+        _ => Err("only `-` is accepted, leave it out to be asked for the secret".to_string()),
+    }
+}
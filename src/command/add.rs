@@ -1,5 +1,9 @@
+use std::path::PathBuf;
+
 use clap::{Args, Subcommand};
 
+use crate::browser::Browser;
+
 #[derive(Debug, Args)]
 pub(crate) struct AddCommand {
     #[command(subcommand)]
@@ -9,5 +13,17 @@ pub(crate) struct AddCommand {
 #[derive(Debug, Subcommand)]
 pub(crate) enum AddCommands {
     /// Add an account from a cookie
-    Account { name: String, cookie: String },
+    Account {
+        name: String,
+        #[arg(required_unless_present = "from_browser")]
+        cookie: Option<String>,
+
+        /// Take the cookie from a browser roblox is logged into
+        #[arg(long, value_enum, conflicts_with = "cookie")]
+        from_browser: Option<Browser>,
+
+        /// The browser profile directory, defaults to the one the browser uses
+        #[arg(long, value_name = "PATH", requires = "from_browser")]
+        profile: Option<PathBuf>,
+    },
 }
@@ -1,15 +1,54 @@
 use clap::{Args, Subcommand};
 
+use crate::{action::join::game::Pick, launcher};
+
 #[derive(Debug, Args)]
 pub(crate) struct JoinCommand {
     #[command(subcommand)]
     pub(crate) command: JoinCommands,
+
+    /// What launches roblox, defaults to `launcher` in the config or `xdg`
+    #[arg(long, global = true, value_enum)]
+    pub(crate) launcher: Option<launcher::Kind>,
+
+    /// Print the `roblox-player:` URI instead of launching it, like `--launcher dry-run`
+    #[arg(long, global = true, conflicts_with = "launcher")]
+    pub(crate) dry_run: bool,
+}
+
+impl JoinCommand {
+    pub(crate) fn launcher(&self) -> Option<launcher::Kind> {
+        match self.dry_run {
+            true => Some(launcher::Kind::DryRun),
+            false => self.launcher,
+        }
+    }
 }
 
 #[derive(Debug, Subcommand)]
 pub(crate) enum JoinCommands {
     /// Joins a roblox game instance
-    Game { id: u64, job_id: Option<String> },
+    Game {
+        id: u64,
+        job_id: Option<String>,
+
+        /// The link code of a private server to join, from its invite link
+        #[arg(long, value_name = "CODE", conflicts_with = "job_id")]
+        private_server: Option<String>,
+
+        /// Pick the public server to join instead of giving its job id
+        #[arg(long, value_enum, conflicts_with_all = ["job_id", "private_server"])]
+        pick: Option<Pick>,
+    },
+
+    /// Joins the server a user is playing in
+    User {
+        /// Id or username of the user
+        user: String,
+    },
+
+    /// Joins the private server a roblox share link leads to
+    Link { url: String },
 
     /// Joins or requests to join a group
     Group { id: u64 },
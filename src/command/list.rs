@@ -12,6 +12,20 @@ pub(crate) struct ListTradeCommand {
     pub(crate) command: ListTradeCommands,
 }
 
+/// Paging options shared by the list commands which fetch their entries page by page
+#[derive(Debug, Default, Args)]
+pub(crate) struct PagingArgs {
+    /// Cursor of the page to start from, as printed in `Next cursor`
+    #[arg(long)]
+    pub(crate) cursor: Option<String>,
+    /// Follow the pages until this many entries were listed
+    #[arg(long, value_name = "N")]
+    pub(crate) limit: Option<usize>,
+    /// Follow the pages until there are none left
+    #[arg(long)]
+    pub(crate) all: bool,
+}
+
 #[derive(Debug, Subcommand)]
 pub(crate) enum ListCommands {
     /// List avatar assets of `user`
@@ -21,12 +35,13 @@ pub(crate) enum ListCommands {
     },
 
     /// List badges of `user` or `place`
-    #[group(multiple = false)]
     Badges {
         #[arg(short, long)]
         user_id: Option<u64>,
-        #[arg(short, long)]
+        #[arg(short, long, conflicts_with = "user_id")]
         place_id: Option<u64>,
+        #[command(flatten)]
+        paging: PagingArgs,
     },
 
     /// List created experiences of the `user` or `group`
@@ -35,32 +50,42 @@ pub(crate) enum ListCommands {
         user_id: Option<u64>,
         #[arg(short, long)]
         group_id: Option<u64>,
+        #[command(flatten)]
+        paging: PagingArgs,
     },
 
-    /// List favorited assets of `user`
+    /// List favorited assets of `user`, `Place` lists the favorited experiences
     Favorites {
         #[arg(short, long)]
         kind: String,
         #[arg(short, long)]
         user_id: Option<u64>,
+        #[command(flatten)]
+        paging: PagingArgs,
     },
 
     /// List the users the `user` is being followed by
     Followers {
         #[arg(short, long)]
         user_id: Option<u64>,
+        #[command(flatten)]
+        paging: PagingArgs,
     },
 
     /// List the users the `user` is following
     Followings {
         #[arg(short, long)]
         user_id: Option<u64>,
+        #[command(flatten)]
+        paging: PagingArgs,
     },
 
     /// List the friends of the `user`
     Friends {
         #[arg(short, long)]
         user_id: Option<u64>,
+        #[command(flatten)]
+        paging: PagingArgs,
     },
 
     /// List gamepasses of `user` or `place`
@@ -86,17 +111,18 @@ pub(crate) enum ListCommands {
 
         #[arg(short, long)]
         verbose: bool,
-        #[arg(short, long)]
-        json: bool,
+        #[command(flatten)]
+        paging: PagingArgs,
     },
 
     /// List username history of `user` or `group`
-    #[group(multiple = false)]
     NameHistory {
         #[arg(short, long)]
         user_id: Option<u64>,
-        #[arg(short, long)]
+        #[arg(short, long, conflicts_with = "user_id")]
         group_id: Option<u64>,
+        #[command(flatten)]
+        paging: PagingArgs,
     },
 
     /// List avatar outfits of `user`
@@ -105,27 +131,49 @@ pub(crate) enum ListCommands {
         user_id: Option<u64>,
     },
 
+    /// List the public servers of `place`
+    Servers {
+        place_id: u64,
+        #[command(flatten)]
+        paging: PagingArgs,
+    },
+
     // Authenticated account only
     /// List messages of account
     Messages,
     /// List chats of account
-    Conversations,
+    Conversations {
+        #[command(flatten)]
+        paging: PagingArgs,
+    },
     /// List notifications of account
     Notifications,
     /// List friend-requests of account
-    FriendRequests,
+    FriendRequests {
+        #[command(flatten)]
+        paging: PagingArgs,
+    },
     /// List trades of account
     Trades(ListTradeCommand),
 }
 
+#[derive(Debug, Args)]
+pub(crate) struct TradeArgs {
+    /// Also list the offered and requested items and robux of every trade
+    #[arg(short, long)]
+    pub(crate) verbose: bool,
+    #[command(flatten)]
+    pub(crate) paging: PagingArgs,
+}
+
 #[derive(Debug, Subcommand)]
 pub(crate) enum ListTradeCommands {
     /// List inbound trades
-    Inbound,
+    Inbound(TradeArgs),
     /// List outbound trades
-    Outbound,
+    Outbound(TradeArgs),
     /// List completed trades
-    Completed,
+    Completed(TradeArgs),
     /// List inactive trades
-    Inactive,
+    Inactive(TradeArgs),
 }
@@ -8,8 +8,17 @@ pub(crate) struct LoginCommand {
 
 #[derive(Debug, Subcommand)]
 pub(crate) enum LoginCommands {
-    /// Create a login token to add to the config accounts list
-    NewQuick,
+    /// Create a quick login code and add the account it gets entered with to the config
+    NewQuick {
+        /// The name to alias the account by
+        name: String,
+    },
     /// Authorize a login request from quick-login
     Authorize { code: String },
+    /// Invalidate the cookie in use and remove its account from the config
+    Logout,
+    /// List the sessions the account is logged into
+    Sessions,
+    /// Sign out every session but the current one, whose cookie gets replaced
+    RevokeOthers,
 }
@@ -1,5 +1,8 @@
+use std::path::PathBuf;
+
 use clap::{Parser, Subcommand};
 
+use account::AccountCommand;
 use add::AddCommand;
 use download::DownloadCommand;
 use info::InfoCommand;
@@ -7,6 +10,9 @@ use join::JoinCommand;
 use list::ListCommand;
 use login::LoginCommand;
 
+use crate::output::{Format, Path, Predicate};
+
+pub(crate) mod account;
 pub(crate) mod add;
 pub(crate) mod download;
 pub(crate) mod info;
@@ -15,14 +21,52 @@ pub(crate) mod list;
 pub(crate) mod login;
 
 #[derive(Debug, Parser)]
-#[command(version, about)]
+#[command(version, about, after_help = crate::error::EXIT_CODES)]
 pub(crate) struct Command {
     #[command(subcommand)]
     pub(crate) command: Commands,
 
     /// The account username to operate on, defaults to the first entry in the config
-    #[arg(long)]
+    #[arg(long, conflicts_with_all = ["cookie", "cookie_file"])]
     pub(crate) account: Option<String>,
+
+    /// Read the cookie from stdin without storing it, only `-` is accepted
+    ///
+    /// The cookie is taken from the first of `--cookie -`, `--cookie-file`, `--account`, the
+    /// `RBX_COOKIE` environment variable and the first account in the config. Cookies from the
+    /// first two and `RBX_COOKIE` are never stored, and the config is not needed for them.
+    #[arg(long, value_name = "-", value_parser = stdin_only, conflicts_with = "cookie_file")]
+    pub(crate) cookie: Option<String>,
+
+    /// Read the cookie from a file without storing it
+    #[arg(long, value_name = "PATH")]
+    pub(crate) cookie_file: Option<PathBuf>,
+
+    /// The format objects are printed in
+    #[arg(long, global = true, value_enum, default_value_t)]
+    pub(crate) format: Format,
+
+    /// Comma separated columns to keep in table and csv output, matched by full or last key segment
+    #[arg(long, global = true, value_delimiter = ',')]
+    pub(crate) columns: Vec<String>,
+
+    /// Comma separated paths to keep, e.g. `Assets[].Asset.Id`
+    #[arg(long, global = true, value_delimiter = ',')]
+    pub(crate) select: Vec<Path>,
+
+    /// Keep only objects or list entries matching, e.g. `Price > 100`, can be repeated
+    #[arg(long = "where", value_name = "PREDICATE", global = true)]
+    pub(crate) filter: Vec<Predicate>,
+}
+
+/// Keeps cookies out of the process list and shell history, where a literal one would end up
+fn stdin_only(value: &str) -> Result<String, String> {
+    match value {
+        "-" => Ok(value.to_owned()),
+        _ => Err(
+            "only `-` is accepted, use --cookie-file or RBX_COOKIE to pass a cookie".to_string(),
+        ),
+    }
 }
 
 #[derive(Debug, Subcommand)]
@@ -32,6 +76,8 @@ pub(crate) enum Commands {
 
     /// Add an object to an instance
     Add(AddCommand),
+    /// Manage the accounts in the config
+    Account(AccountCommand),
     /// Print the info of an object
     Info(InfoCommand),
     /// Join a specific instance (game, group, etc.)
@@ -1,5 +1,12 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    error::{Context, Result},
+    launcher,
+    secret::{self, Reference},
+};
+
 // TODO: add Custom type
 #[derive(Clone, Default, Deserialize, Serialize)]
 pub(crate) enum DownloadPathKind {
@@ -11,13 +18,42 @@ pub(crate) enum DownloadPathKind {
 #[derive(Clone, Default, Deserialize, Serialize)]
 pub(crate) struct Account {
     pub(crate) name: String,
-    pub(crate) cookie: String,
+    /// Only kept here by the plain backend, see `secret`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) cookie: Option<String>,
+    /// Where the cookie is kept by the other backends
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) secret: Option<Reference>,
+    /// When the cookie stops working, only known for cookies roblox handed out to this tool
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) expires: Option<DateTime<Utc>>,
+    /// Id of the user the cookie belongs to, missing for accounts added before it was recorded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) id: Option<u64>,
+    /// The real username of the user, `name` is only the alias
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) username: Option<String>,
+    /// The authenticator secret answering two step verification, only kept here by the plain backend
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) totp_secret: Option<String>,
+    /// Where the authenticator secret is kept by the other backends
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) totp_reference: Option<Reference>,
 }
 
 #[derive(Clone, Deserialize, Serialize)]
 pub(crate) struct Config {
     pub(crate) accounts: Vec<Account>,
     pub(crate) download_path_type: Option<DownloadPathKind>,
+    /// Where the cookies of new accounts are stored, only set once `account migrate` picked one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) secret_backend: Option<secret::Backend>,
+    /// What joins games when `--launcher` isn't given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) launcher: Option<launcher::Kind>,
+    /// The command the custom launcher runs, `{uri}` is replaced by the `roblox-player:` URI
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) launch_command: Option<String>,
 }
 
 impl ::std::default::Default for Config {
@@ -25,6 +61,32 @@ impl ::std::default::Default for Config {
         Self {
             accounts: Vec::new(),
             download_path_type: Some(DownloadPathKind::default()),
+            secret_backend: None,
+            launcher: None,
+            launch_command: None,
         }
     }
 }
+
+impl Config {
+    /// Where the cookies of new accounts are stored, see `secret::Backend::preferred`
+    pub(crate) fn backend(&self) -> secret::Backend {
+        self.secret_backend
+            .unwrap_or_else(secret::Backend::preferred)
+    }
+
+    /// Index of the account aliased `name`, ignoring case
+    pub(crate) fn position(&self, name: &str) -> Option<usize> {
+        self.accounts
+            .iter()
+            .position(|x| x.name.to_lowercase() == name.to_lowercase())
+    }
+}
+
+pub(crate) fn load() -> Result<Config> {
+    confy::load(env!("CARGO_BIN_NAME"), Some("config")).context("failed to load config")
+}
+
+pub(crate) fn store(cfg: &Config) -> Result<()> {
+    confy::store(env!("CARGO_BIN_NAME"), Some("config"), cfg).context("failed to store config")
+}
@@ -0,0 +1,218 @@
+use std::{fmt, io};
+
+use console::style;
+use roblox_api::ApiError;
+
+/// The exit codes of the failures, shown at the end of `--help`
+pub(crate) const EXIT_CODES: &str = "\
+Exit codes:
+  0  Success
+  1  Roblox returned an unexpected error
+  2  Invalid arguments or config
+  3  The account cookie is invalid, expired or the account is locked
+  4  The requested object doesn't exist
+  5  The requested object is private
+  6  Rate limited by roblox
+  7  Roblox could not be reached
+  8  Reading or writing a local file failed";
+
+/// Everything that can make a command fail, every variant carries the message printed to the user
+#[derive(Debug)]
+pub(crate) enum Error {
+    Api(String),
+    Config(String),
+    Auth(String),
+    NotFound(String),
+    Private(String),
+    RateLimited(String),
+    Network(String),
+    Io(String),
+}
+
+pub(crate) type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl Error {
+    pub(crate) fn exit_code(&self) -> u8 {
+        match self {
+            Self::Api(_) => 1,
+            Self::Config(_) => 2,
+            Self::Auth(_) => 3,
+            Self::NotFound(_) => 4,
+            Self::Private(_) => 5,
+            Self::RateLimited(_) => 6,
+            Self::Network(_) => 7,
+            Self::Io(_) => 8,
+        }
+    }
+
+    fn message(&self) -> &str {
+        match self {
+            Self::Api(message)
+            | Self::Config(message)
+            | Self::Auth(message)
+            | Self::NotFound(message)
+            | Self::Private(message)
+            | Self::RateLimited(message)
+            | Self::Network(message)
+            | Self::Io(message) => message,
+        }
+    }
+
+    fn map_message(self, f: impl FnOnce(String) -> String) -> Self {
+        match self {
+            Self::Api(message) => Self::Api(f(message)),
+            Self::Config(message) => Self::Config(f(message)),
+            Self::Auth(message) => Self::Auth(f(message)),
+            Self::NotFound(message) => Self::NotFound(f(message)),
+            Self::Private(message) => Self::Private(f(message)),
+            Self::RateLimited(message) => Self::RateLimited(f(message)),
+            Self::Network(message) => Self::Network(f(message)),
+            Self::Io(message) => Self::Io(f(message)),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ApiError> for Error {
+    fn from(error: ApiError) -> Self {
+        match error {
+            ApiError::Unauthorized | ApiError::TokenValidation => {
+                Self::Auth("account cookie is invalid or expired".to_string())
+            }
+
+            ApiError::AccontLocked | ApiError::AccountIssue | ApiError::PinIsLocked => {
+                Self::Auth("account is locked".to_string())
+            }
+
+            ApiError::InvalidCredentials
+            | ApiError::UnverifiedCredentials
+            | ApiError::CaptchaFailed
+            | ApiError::ChallengeRequired(_)
+            | ApiError::ChallengeFailed
+            | ApiError::InvalidChallengeId
+            | ApiError::InvalidTwoStepVerificationCode => Self::Auth(error.to_string()),
+
+            ApiError::Ratelimited => Self::RateLimited("too many requests".to_string()),
+            ApiError::PermissionError => Self::Private("permission denied".to_string()),
+
+            ApiError::InvalidUser | ApiError::InvalidUserId => {
+                Self::NotFound("user not found".to_string())
+            }
+
+            ApiError::InvalidAssetId => Self::NotFound("asset not found".to_string()),
+            ApiError::InvalidBadge => Self::NotFound("badge not found".to_string()),
+
+            ApiError::Unknown(code, message) => {
+                let message = message.unwrap_or_else(|| format!("status code {code}"));
+                match code {
+                    401 => Self::Auth(message),
+                    403 => Self::Private(message),
+                    404 => Self::NotFound(message),
+                    429 => Self::RateLimited(message),
+                    _ => Self::Api(message),
+                }
+            }
+
+            ApiError::Multiple(errors) => match errors.into_iter().next() {
+                Some(error) => Self::from(error),
+                None => Self::Api("unknown error".to_string()),
+            },
+
+            error => Self::Api(error.to_string()),
+        }
+    }
+}
+
+impl From<roblox_api::Error> for Error {
+    fn from(error: roblox_api::Error) -> Self {
+        match error {
+            roblox_api::Error::ApiError(error) => Self::from(error),
+            roblox_api::Error::BadJson => Self::Api("unexpected response from roblox".to_string()),
+            roblox_api::Error::IoError(error) => Self::from(error),
+            roblox_api::Error::ReqwestError(error) => Self::from(error),
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(error: reqwest::Error) -> Self {
+        match error.status().map(|status| status.as_u16()) {
+            Some(code) => Self::from(ApiError::Unknown(code, Some(error.to_string()))),
+            None => Self::Network(error.to_string()),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error.to_string())
+    }
+}
+
+impl From<confy::ConfyError> for Error {
+    fn from(error: confy::ConfyError) -> Self {
+        Self::Config(error.to_string())
+    }
+}
+
+/// Prints `error` as a styled `error:` line on stderr
+pub(crate) fn print(error: &Error) {
+    eprintln!("{} {}", style("error:").red().bold(), style(error).bold());
+}
+
+/// Prints every failure of `results` but the last, which is returned to decide the exit code, for
+/// commands which go on with the other accounts when one fails
+pub(crate) fn report_all(results: impl IntoIterator<Item = Result<()>>) -> Result<()> {
+    let mut failure = None;
+    for result in results {
+        if let Err(error) = result
+            && let Some(previous) = failure.replace(error)
+        {
+            print(&previous);
+        }
+    }
+
+    failure.map_or(Ok(()), Err)
+}
+
+pub(crate) trait Context<T> {
+    /// Prefixes the message of the error with what was being done, like `failed to get badges`
+    fn context(self, context: &str) -> Result<T>;
+}
+
+impl<T, E: Into<Error>> Context<T> for Result<T, E> {
+    fn context(self, context: &str) -> Result<T> {
+        self.map_err(|error| {
+            error
+                .into()
+                .map_message(|message| format!("{context}: {message}"))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use roblox_api::ApiError;
+
+    use super::{Context, Error};
+
+    #[test]
+    fn api_error_kinds() {
+        let error = Err::<(), _>(roblox_api::Error::ApiError(ApiError::Unknown(404, None)))
+            .context("failed to get user")
+            .unwrap_err();
+
+        assert_eq!(error.exit_code(), 4);
+        assert_eq!(error.to_string(), "failed to get user: status code 404");
+
+        let error = Error::from(ApiError::Multiple(vec![ApiError::Ratelimited]));
+        assert!(matches!(error, Error::RateLimited(_)));
+    }
+}
@@ -0,0 +1,175 @@
+//! The clients a `roblox-player:` URI can be handed to
+
+use std::{fmt, process::Command};
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::Config,
+    error::{Context, Error, Result},
+};
+
+const SOBER_APP_ID: &str = "org.vinegarhq.Sober";
+const VINEGAR_APP_ID: &str = "org.vinegarhq.Vinegar";
+
+/// What replaces the URI in `launch_command`
+const URI_PLACEHOLDER: &str = "{uri}";
+
+/// Starts roblox with a `roblox-player:` URI
+pub(crate) trait Launcher {
+    fn launch(&self, uri: &str) -> Result<()>;
+
+    /// Whether the URI has to carry a real authentication ticket, which logs the account in for
+    /// whoever gets hold of it
+    fn needs_ticket(&self) -> bool {
+        true
+    }
+}
+
+/// Which launcher joins games, picked with `--launcher` or `launcher` in the config
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum Kind {
+    /// Whatever handles `roblox-player:` links, through `xdg-open`
+    #[default]
+    Xdg,
+    /// The Sober flatpak
+    Sober,
+    /// The Vinegar flatpak, which runs the windows client in Wine
+    Vinegar,
+    /// `launch_command` from the config, run by `sh` with `{uri}` replaced
+    Custom,
+    /// Only prints the URI
+    DryRun,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Xdg => write!(f, "xdg"),
+            Self::Sober => write!(f, "sober"),
+            Self::Vinegar => write!(f, "vinegar"),
+            Self::Custom => write!(f, "custom"),
+            Self::DryRun => write!(f, "dry-run"),
+        }
+    }
+}
+
+struct Xdg;
+struct Flatpak {
+    kind: Kind,
+    app_id: &'static str,
+    /// Passed before the URI
+    args: &'static [&'static str],
+}
+struct Custom(String);
+struct DryRun;
+
+impl Launcher for Xdg {
+    #[cfg(target_family = "unix")]
+    fn launch(&self, uri: &str) -> Result<()> {
+        run(Command::new("xdg-open").arg(uri), Kind::Xdg)
+    }
+
+    #[cfg(not(target_family = "unix"))]
+    fn launch(&self, _uri: &str) -> Result<()> {
+        Err(Error::Config(
+            "the xdg launcher needs xdg-open, use `--launcher custom` or `--dry-run` instead"
+                .to_string(),
+        ))
+    }
+}
+
+impl Launcher for Flatpak {
+    fn launch(&self, uri: &str) -> Result<()> {
+        run(
+            Command::new("flatpak")
+                .args(["run", self.app_id])
+                .args(self.args)
+                .arg(uri),
+            self.kind,
+        )
+    }
+}
+
+impl Launcher for Custom {
+    fn launch(&self, uri: &str) -> Result<()> {
+        // The URI is passed as an argument so it never has to be quoted into the script
+        run(
+            Command::new("sh")
+                .args(["-c", &script(&self.0), env!("CARGO_BIN_NAME")])
+                .arg(uri),
+            Kind::Custom,
+        )
+    }
+}
+
+impl Launcher for DryRun {
+    fn launch(&self, uri: &str) -> Result<()> {
+        println!("{uri}");
+        Ok(())
+    }
+
+    fn needs_ticket(&self) -> bool {
+        false
+    }
+}
+
+/// The launcher `kind` stands for, the one from the config when it's not given
+pub(crate) fn new(kind: Option<Kind>, cfg: &Config) -> Result<Box<dyn Launcher>> {
+    Ok(match kind.or(cfg.launcher).unwrap_or_default() {
+        Kind::Xdg => Box::new(Xdg),
+        Kind::Sober => Box::new(Flatpak {
+            kind: Kind::Sober,
+            app_id: SOBER_APP_ID,
+            args: &[],
+        }),
+        Kind::Vinegar => Box::new(Flatpak {
+            kind: Kind::Vinegar,
+            app_id: VINEGAR_APP_ID,
+            args: &["run"],
+        }),
+        Kind::Custom => Box::new(Custom(cfg.launch_command.clone().ok_or(Error::Config(
+            "the custom launcher needs `launch_command` in the config".to_string(),
+        ))?)),
+        Kind::DryRun => Box::new(DryRun),
+    })
+}
+
+/// `template` as a script taking the URI as `$1`, which is appended when it has no placeholder
+fn script(template: &str) -> String {
+    if template.contains(URI_PLACEHOLDER) {
+        template.replace(URI_PLACEHOLDER, "\"$1\"")
+    } else {
+        format!("{template} \"$1\"")
+    }
+}
+
+fn run(command: &mut Command, kind: Kind) -> Result<()> {
+    let context = format!("failed to launch roblox with the {kind} launcher");
+    let status = command
+        .spawn()
+        .context(&context)?
+        .wait()
+        .context(&context)?;
+
+    match status.success() {
+        true => Ok(()),
+        false => Err(Error::Io(format!("{context}: exited with {status}"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::script;
+
+    #[test]
+    fn custom_scripts() {
+        assert_eq!(
+            script("wine ~/roblox/RobloxPlayerBeta.exe {uri} --fast"),
+            "wine ~/roblox/RobloxPlayerBeta.exe \"$1\" --fast"
+        );
+        assert_eq!(script("my-launcher --uri"), "my-launcher --uri \"$1\"");
+    }
+}
@@ -1,11 +1,22 @@
 mod action;
+mod browser;
 mod command;
 mod conclusion;
 mod config;
+mod error;
+mod launcher;
 mod object;
 mod objects;
-
-use std::ops::Not;
+mod output;
+mod qr;
+mod secret;
+mod web;
+
+use std::{
+    env, fs,
+    io::{self, Read},
+    process::ExitCode,
+};
 
 use clap::Parser;
 use roblox_api::{
@@ -15,148 +26,306 @@ use roblox_api::{
 };
 
 use command::{
-    Command, Commands, add::AddCommands, download::DownloadCommands, info::InfoCommands,
-    join::JoinCommands, list::ListCommands, login::LoginCommands,
+    Command, Commands,
+    account::AccountCommands,
+    add::AddCommands,
+    download::DownloadCommands,
+    info::InfoCommands,
+    join::JoinCommands,
+    list::{ListCommands, ListTradeCommands},
+    login::LoginCommands,
 };
-use config::{Account, Config};
+use config::Config;
+use error::{Context, Error, Result};
+use web::{WebClient, trades::TradeStatusType};
+
+/// Used as the cookie when neither `--cookie`, `--cookie-file` nor `--account` are given
+const COOKIE_VAR: &str = "RBX_COOKIE";
 
 #[tokio::main]
-async fn main() {
+async fn main() -> ExitCode {
     let cli = Command::parse();
-    let mut cfg: Config = confy::load(env!("CARGO_BIN_NAME"), Some("config")).unwrap();
-
-    let account = match cli.account {
-        Some(name) => {
-            let mut filter = cfg
-                .accounts
-                .iter()
-                .filter(|x| x.name.to_lowercase() == name.to_lowercase());
-            filter
-                .next()
-                .expect(&format!("error: account with username: {name} not found"))
+    output::init(output::Options {
+        format: cli.format,
+        columns: cli.columns.clone(),
+        select: cli.select.clone(),
+        filter: cli.filter.clone(),
+    });
+
+    match run(&cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            error::print(&error);
+            ExitCode::from(error.exit_code())
         }
+    }
+}
 
-        _ => cfg.accounts.first().expect("error: no account entry found"),
+/// The cookie given for this run only, see `--cookie` for the order
+fn ephemeral_cookie(cli: &Command) -> Result<Option<String>> {
+    // `--cookie` only takes `-`, which clap already checked
+    let (source, cookie) = if cli.cookie.is_some() {
+        let mut cookie = String::new();
+        io::stdin()
+            .read_to_string(&mut cookie)
+            .context("failed to read the cookie from stdin")?;
+
+        ("stdin".to_string(), cookie)
+    } else if let Some(path) = &cli.cookie_file {
+        let cookie = fs::read_to_string(path).context("failed to read the cookie file")?;
+        (path.display().to_string(), cookie)
+    } else if cli.account.is_none()
+        && let Ok(cookie) = env::var(COOKIE_VAR)
+    {
+        (COOKIE_VAR.to_string(), cookie)
+    } else {
+        return Ok(None);
     };
 
-    let mut client = Client::from_cookie(Cookie::from(account.cookie.as_str()));
+    let cookie = cookie.trim();
+    if cookie.is_empty() {
+        return Err(Error::Config(format!("the cookie from {source} is empty")));
+    }
+
+    Ok(Some(cookie.to_owned()))
+}
+
+async fn run(cli: &Command) -> Result<()> {
+    let ephemeral = ephemeral_cookie(cli)?;
 
+    // An ephemeral cookie has to work where the config can't even be created, like CI containers
+    let cfg = match config::load() {
+        Err(_) if ephemeral.is_some() => Config::default(),
+        cfg => cfg?,
+    };
+
+    // These only work on the config, so they don't need an account to be selected
     match &cli.command {
-        Commands::Status => {
-            action::status::print(&cfg).await;
-        }
+        Commands::Status => return action::status::print(&cfg).await,
 
         Commands::Add(add) => match &add.command {
-            AddCommands::Account { name, cookie } => {
-                let unique_name = cfg
-                    .accounts
-                    .iter()
-                    .any(|x| x.name.to_lowercase() == *name.to_lowercase())
-                    .not();
-
-                if !unique_name {
-                    return eprintln!("error: account with username: {name} already exists");
+            AddCommands::Account {
+                name,
+                cookie,
+                from_browser,
+                profile,
+            } => {
+                let cookie = match (cookie, from_browser) {
+                    (Some(cookie), _) => cookie.to_owned(),
+                    (None, Some(browser)) => browser::cookie(*browser, profile.as_deref())?,
+                    (None, None) => unreachable!("clap requires one of them"),
+                };
+
+                return action::account::add(cfg, name, &cookie, None).await;
+            }
+        },
+
+        Commands::Login(login) => {
+            if let LoginCommands::NewQuick { name } = &login.command {
+                return action::login::quick_login(cfg, name).await;
+            }
+        }
+
+        Commands::Account(account) => {
+            return match &account.command {
+                AccountCommands::List => {
+                    action::account::list(&cfg);
+                    Ok(())
                 }
 
-                cfg.accounts.push(Account {
-                    name: name.to_owned().to_lowercase(),
-                    cookie: cookie.to_owned(),
-                });
+                AccountCommands::Remove { name } => action::account::remove(cfg, name),
+                AccountCommands::Rename { name, new_name } => {
+                    action::account::rename(cfg, name, new_name)
+                }
 
-                confy::store(env!("CARGO_BIN_NAME"), Some("config"), cfg).unwrap();
+                AccountCommands::SetDefault { name } => action::account::set_default(cfg, name),
+                AccountCommands::Refresh { within, all } => {
+                    action::account::refresh(cfg, *within, *all).await
+                }
 
-                println!("info: added account: {name} to the list");
-            }
-        },
+                AccountCommands::Migrate { backend } => action::account::migrate(cfg, *backend),
+                AccountCommands::SetTotp {
+                    name,
+                    secret,
+                    clear,
+                } => action::account::set_totp(cfg, name, secret.is_some(), *clear),
+            };
+        }
+
+        _ => {}
+    }
+
+    let (account, cookie) = match ephemeral {
+        Some(cookie) => (None, cookie),
+        None => {
+            let account =
+                match &cli.account {
+                    Some(name) => cfg.position(name).map(|index| &cfg.accounts[index]).ok_or(
+                        Error::Config(format!("account with username: {name} not found")),
+                    )?,
+
+                    _ => cfg.accounts.first().ok_or(Error::Config(format!(
+                        "no account entry found, add one with `add account` or set {COOKIE_VAR}"
+                    )))?,
+                };
+
+            (Some(account), secret::load(account)?)
+        }
+    };
+
+    let mut client = Client::from_cookie(Cookie::from(cookie.as_str()));
+    let mut web = WebClient::from_cookie(&cookie);
+
+    match &cli.command {
+        Commands::Status | Commands::Add(_) | Commands::Account(_) => {
+            unreachable!("handled before selecting an account")
+        }
 
         Commands::Info(info) => match &info.command {
             InfoCommands::Asset { id } => {
-                action::info::asset(&mut client, *id).await;
+                action::info::asset(&mut client, *id).await?;
             }
 
             InfoCommands::User { id } => {
-                action::info::user(&mut client, *id).await;
+                action::info::user(&mut client, *id).await?;
             }
 
             InfoCommands::Group { id } => {
-                action::info::group(&mut client, *id).await;
+                action::info::group(&mut client, *id).await?;
             }
 
-            InfoCommands::Game { id } => action::info::place(&mut client, *id).await,
-            InfoCommands::Gamepass { id } => action::info::gamepass(&mut client, *id).await,
+            InfoCommands::Game { id } => action::info::place(&mut client, *id).await?,
+            InfoCommands::Gamepass { id } => action::info::gamepass(&mut client, *id).await?,
 
-            InfoCommands::Badge { id } => action::info::badge(&mut client, *id).await,
+            InfoCommands::Badge { id } => action::info::badge(&mut client, *id).await?,
         },
 
-        Commands::Join(join) => match &join.command {
-            JoinCommands::Game { id, job_id } => {
-                action::join::game::run(account, *id, job_id.as_deref())
-            }
+        Commands::Join(join) => {
+            let launcher = || launcher::new(join.launcher(), &cfg);
+
+            match &join.command {
+                JoinCommands::Game {
+                    id,
+                    job_id,
+                    private_server,
+                    pick,
+                } => {
+                    let launcher = launcher()?;
+                    let job_id = match pick {
+                        Some(pick) => {
+                            Some(action::join::game::pick_server(&mut web, *id, *pick).await?)
+                        }
+                        None => job_id.clone(),
+                    };
+
+                    action::join::game::run(
+                        &mut client,
+                        &mut web,
+                        account,
+                        launcher.as_ref(),
+                        *id,
+                        job_id.as_deref(),
+                        private_server.as_deref(),
+                    )
+                    .await?
+                }
 
-            JoinCommands::Group { id } => action::join::group(&mut client, *id).await,
-        },
+                JoinCommands::User { user } => {
+                    let launcher = launcher()?;
+                    action::join::user(&mut client, &mut web, account, launcher.as_ref(), user)
+                        .await?
+                }
+
+                JoinCommands::Link { url } => {
+                    let launcher = launcher()?;
+                    action::join::game::link(&mut client, &mut web, account, launcher.as_ref(), url)
+                        .await?
+                }
+
+                JoinCommands::Group { id } => {
+                    action::join::group(&mut client, &mut web, account, *id).await?
+                }
+            }
+        }
 
         Commands::Download(download) => match &download.command {
-            DownloadCommands::Asset { id } => action::download::asset(&mut client, &cfg, *id).await,
+            DownloadCommands::Asset { id } => {
+                action::download::asset(&mut client, &cfg, *id).await?
+            }
 
             DownloadCommands::Thumbnail { kind, id, size } => {
                 let kind = ThumbnailRequestType::try_from(kind.as_str())
-                    .expect("error: unknown thumnbail kind");
+                    .map_err(|_| Error::Config(format!("unknown thumbnail kind: {kind}")))?;
                 let size = match size {
                     Some(size) => ThumbnailSize::try_from(size.as_str())
-                        .expect("error: unknown thumbnail size"),
+                        .map_err(|_| Error::Config(format!("unknown thumbnail size: {size}")))?,
                     _ => ThumbnailSize::S420x420,
                 };
 
-                action::download::thumbnail(&mut client, &cfg, *id, kind, size).await;
+                action::download::thumbnail(&mut client, &cfg, *id, kind, size).await?;
             }
         },
 
         Commands::List(list) => match &list.command {
             ListCommands::Avatar { user_id } => {
-                action::list::avatar(&mut client, *user_id).await;
+                action::list::avatar(&mut client, *user_id).await?;
             }
 
-            ListCommands::Badges { user_id, place_id } => {
+            ListCommands::Badges {
+                user_id,
+                place_id,
+                paging,
+            } => {
                 if let Some(place_id) = place_id {
-                    action::list::badges::place(&mut client, *place_id).await;
+                    action::list::badges::place(&mut client, *place_id, paging).await?;
                 } else {
-                    action::list::badges::user(&mut client, *user_id).await;
+                    action::list::badges::user(&mut client, *user_id, paging).await?;
                 }
             }
 
-            ListCommands::Experiences { user_id, group_id } => {
+            ListCommands::Experiences {
+                user_id,
+                group_id,
+                paging,
+            } => {
                 if let Some(group_id) = group_id {
-                    action::list::experiences::group(&mut client, *group_id).await;
+                    action::list::experiences::group(&mut client, *group_id, paging).await?;
                 } else {
-                    action::list::experiences::user(&mut client, *user_id).await;
+                    action::list::experiences::user(&mut client, *user_id, paging).await?;
                 }
             }
 
-            ListCommands::Favorites { user_id, kind } => {
-                let kind = AssetTypeId::try_from(kind.as_str()).expect("error: unknown asset kind");
-                action::list::favorites(&mut client, *user_id, kind).await;
+            ListCommands::Favorites {
+                user_id,
+                kind,
+                paging,
+            } => {
+                let kind = AssetTypeId::try_from(kind.as_str())
+                    .map_err(|_| Error::Config(format!("unknown asset kind: {kind}")))?;
+                action::list::favorites(&mut client, &mut web, *user_id, kind, paging).await?;
             }
 
-            ListCommands::Followers { user_id } => {
-                action::list::followers(&mut client, *user_id).await
+            ListCommands::Followers { user_id, paging } => {
+                action::list::followers(&mut client, &mut web, *user_id, paging).await?
             }
 
-            ListCommands::Followings { user_id } => {
-                action::list::followings(&mut client, *user_id).await
+            ListCommands::Followings { user_id, paging } => {
+                action::list::followings(&mut client, &mut web, *user_id, paging).await?
             }
 
-            ListCommands::Friends { user_id } => action::list::friends(&mut client, *user_id).await,
+            ListCommands::Friends { user_id, paging } => {
+                action::list::friends(&mut client, *user_id, paging).await?
+            }
 
             ListCommands::Groups { user_id } => {
-                action::list::groups(&mut client, *user_id).await;
+                action::list::groups(&mut client, *user_id).await?;
             }
 
             ListCommands::Gamepasses { user_id, place_id } => {
                 if let Some(place_id) = place_id {
-                    action::list::gamepasses::place(&mut client, *place_id).await;
+                    action::list::gamepasses::place(&mut client, *place_id).await?;
                 } else {
-                    action::list::gamepasses::user(&mut client, *user_id).await;
+                    action::list::gamepasses::user(&mut client, *user_id).await?;
                 }
             }
 
@@ -164,46 +333,80 @@ async fn main() {
                 user_id,
                 kind,
                 verbose,
-                json,
+                paging,
             } => {
-                let kind = AssetTypeId::try_from(kind.as_str()).expect("error: unknown asset kind");
-                action::list::inventory(&mut client, *user_id, kind, *verbose, *json).await;
+                let kind = AssetTypeId::try_from(kind.as_str())
+                    .map_err(|_| Error::Config(format!("unknown asset kind: {kind}")))?;
+                action::list::inventory(&mut client, &cookie, *user_id, kind, *verbose, paging)
+                    .await?;
             }
 
-            ListCommands::NameHistory { user_id, group_id } => {
+            ListCommands::NameHistory {
+                user_id,
+                group_id,
+                paging,
+            } => {
                 if let Some(group_id) = group_id {
-                    action::list::name_history::group(&mut client, *group_id).await;
+                    action::list::name_history::group(&mut web, *group_id, paging).await?;
                 } else {
-                    action::list::name_history::user(&mut client, *user_id).await;
+                    action::list::name_history::user(&mut client, *user_id, paging).await?;
                 }
             }
 
             ListCommands::Outfits { user_id } => {
-                action::list::outfits(&mut client, *user_id).await;
+                action::list::outfits(&mut client, *user_id).await?;
+            }
+
+            ListCommands::Servers { place_id, paging } => {
+                action::list::servers(&mut web, *place_id, paging).await?;
             }
 
             ListCommands::Messages => {
-                action::list::messages(&mut client).await;
+                action::list::messages(&mut client).await?;
             }
 
-            ListCommands::Conversations => {
-                action::list::conversations(&mut client).await;
+            ListCommands::Conversations { paging } => {
+                action::list::conversations(&mut client, paging).await?;
             }
 
             ListCommands::Notifications => {
-                action::list::notificatons(&mut client).await;
+                action::list::notificatons(&mut client).await?;
             }
 
-            ListCommands::FriendRequests => action::list::friend_requests(&mut client).await,
+            ListCommands::FriendRequests { paging } => {
+                action::list::friend_requests(&mut client, paging).await?
+            }
 
-            ListCommands::Trades(trades) => todo!(),
+            ListCommands::Trades(trades) => {
+                let (kind, args) = match &trades.command {
+                    ListTradeCommands::Inbound(args) => (TradeStatusType::Inbound, args),
+                    ListTradeCommands::Outbound(args) => (TradeStatusType::Outbound, args),
+                    ListTradeCommands::Completed(args) => (TradeStatusType::Completed, args),
+                    ListTradeCommands::Inactive(args) => (TradeStatusType::Inactive, args),
+                };
+
+                action::list::trades(&mut web, kind, args.verbose, &args.paging).await?;
+            }
         },
 
         Commands::Login(login) => match &login.command {
-            LoginCommands::NewQuick => action::login::quick_login(&mut client, &account).await,
+            LoginCommands::NewQuick { .. } => {
+                unreachable!("handled before selecting an account")
+            }
+
             LoginCommands::Authorize { code } => {
-                action::login::authorize_login(&mut client, &code).await
+                action::login::authorize_login(&mut client, code).await?
+            }
+
+            LoginCommands::Logout => {
+                action::login::logout(&mut client, &mut web, &cfg, account).await?
+            }
+            LoginCommands::Sessions => action::login::sessions(&mut web).await?,
+            LoginCommands::RevokeOthers => {
+                action::login::revoke_others(&mut client, &mut web, &cfg, account).await?
             }
         },
     }
+
+    Ok(())
 }
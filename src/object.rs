@@ -1,13 +1,21 @@
 use std::fmt::Write;
 
+use chrono::{DateTime, FixedOffset};
 use console::{Color, style};
+use serde::{Serialize, Serializer};
 
 use crate::conclusion::Conclusion;
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq)]
 pub(crate) enum Value {
+    Null,
     Bool(bool),
+    Integer(i128),
+    Float(f64),
     String(String),
+    /// A string picked from a known set, printed with `FieldStyle::Enum` unless overridden
+    Enum(String),
+    Timestamp(DateTime<FixedOffset>),
     Object(Object),
     Vector(Vec<Value>),
 }
@@ -21,17 +29,17 @@ pub(crate) enum FieldStyle {
     Description,
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub(crate) struct Object {
     fields: Vec<Field>,
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub(crate) struct ObjectBuilder {
     object: Object,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq)]
 pub(crate) struct Field {
     pub(crate) key: String,
     pub(crate) value: Value,
@@ -41,8 +49,12 @@ pub(crate) struct Field {
 impl std::fmt::Display for Value {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Value::Null => write!(f, "None"),
             Value::Bool(value) => write!(f, "{}", value),
-            Value::String(value) => write!(f, "{}", value),
+            Value::Integer(value) => write!(f, "{}", value),
+            Value::Float(value) => write!(f, "{}", value),
+            Value::String(value) | Value::Enum(value) => write!(f, "{}", value),
+            Value::Timestamp(value) => write!(f, "{}", value.format("%Y-%m-%d %H:%M:%S %:z")),
             Value::Object(object) => object.fmt(f),
             Value::Vector(values) => {
                 write!(f, "[")?;
@@ -65,12 +77,33 @@ impl std::fmt::Display for Object {
     }
 }
 
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Value::Null => serializer.serialize_none(),
+            Value::Bool(value) => serializer.serialize_bool(*value),
+            Value::Integer(value) => serializer.serialize_i128(*value),
+            Value::Float(value) => serializer.serialize_f64(*value),
+            Value::String(value) | Value::Enum(value) => serializer.serialize_str(value),
+            Value::Timestamp(value) => serializer.serialize_str(&value.to_rfc3339()),
+            Value::Object(object) => object.serialize(serializer),
+            Value::Vector(values) => serializer.collect_seq(values),
+        }
+    }
+}
+
+impl Serialize for Object {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.fields.iter().map(|field| (&field.key, &field.value)))
+    }
+}
+
 macro_rules! impl_from_primitives_for_value {
-    ($($t:ty),* $(,)?) => {
+    ($variant:ident as $as:ty: $($t:ty),* $(,)?) => {
         $(
             impl From<$t> for Value {
                 fn from(v: $t) -> Self {
-                    Value::String(v.to_string())
+                    Value::$variant(v as $as)
                 }
             }
         )*
@@ -78,8 +111,56 @@ macro_rules! impl_from_primitives_for_value {
 }
 
 impl_from_primitives_for_value!(
-    u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64, &str, String
+    Integer as i128: u8, u16, u32, u64, usize, i8, i16, i32, i64, isize
 );
+impl_from_primitives_for_value!(Float as f64: f64);
+
+impl From<f32> for Value {
+    fn from(value: f32) -> Self {
+        // Going through the shortest representation keeps 0.1f32 from turning into 0.10000000149
+        Self::Float(value.to_string().parse().unwrap_or(value as f64))
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&roblox_api::DateTime> for Value {
+    fn from(value: &roblox_api::DateTime) -> Self {
+        let value = value.to_string();
+        match DateTime::parse_from_rfc3339(&value) {
+            Ok(timestamp) => Self::Timestamp(timestamp),
+            Err(_) => Self::String(value),
+        }
+    }
+}
+
+impl From<roblox_api::DateTime> for Value {
+    fn from(value: roblox_api::DateTime) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl<T> From<Option<T>> for Value
+where
+    T: Into<Value>,
+{
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => value.into(),
+            None => Self::Null,
+        }
+    }
+}
 
 impl From<bool> for Value {
     fn from(value: bool) -> Self {
@@ -103,6 +184,32 @@ where
 }
 
 impl Object {
+    pub(crate) fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+
+    /// Flattens nested objects into their leaf values, keyed by the dotted path to them
+    pub(crate) fn flatten(&self) -> Vec<(String, &Value)> {
+        let mut leaves = Vec::new();
+        self.flatten_into("", &mut leaves);
+        leaves
+    }
+
+    fn flatten_into<'a>(&'a self, prefix: &str, leaves: &mut Vec<(String, &'a Value)>) {
+        for field in &self.fields {
+            let key = if prefix.is_empty() {
+                field.key.to_owned()
+            } else {
+                format!("{prefix}.{}", field.key)
+            };
+
+            match &field.value {
+                Value::Object(object) => object.flatten_into(&key, leaves),
+                value => leaves.push((key, value)),
+            }
+        }
+    }
+
     fn pretty_print(&self, indentation: u32, w: bool, f: &mut String) -> std::fmt::Result {
         for field in &self.fields {
             if w {
@@ -121,7 +228,12 @@ impl Object {
                     )?;
                 }
 
-                Value::String(value) => match &field.style {
+                value @ (Value::Null
+                | Value::Integer(_)
+                | Value::Float(_)
+                | Value::String(_)
+                | Value::Enum(_)
+                | Value::Timestamp(_)) => match field.display_style() {
                     FieldStyle::Auto => {
                         writeln!(
                             f,
@@ -154,7 +266,7 @@ impl Object {
                     }
 
                     FieldStyle::Description => {
-                        if value.is_empty() {
+                        if matches!(value, Value::Null) || value.to_string().is_empty() {
                             writeln!(f)?;
                         } else {
                             writeln!(
@@ -245,8 +357,19 @@ impl Field {
         self.style = style;
         self
     }
+
+    /// The explicitly set style, or the one implied by the type of the value
+    pub(crate) fn display_style(&self) -> FieldStyle {
+        match (self.style, &self.value) {
+            (FieldStyle::Auto, Value::Enum(_)) => FieldStyle::Enum,
+            // Off sale items have no price, which shouldn't be shown as one
+            (FieldStyle::Price, Value::Null) => FieldStyle::Auto,
+            (style, _) => style,
+        }
+    }
 }
 
+#[allow(clippy::module_inception)]
 mod object {
     #[macro_export]
     macro_rules! object {
@@ -325,7 +448,7 @@ mod tests {
             ))
             .build();
 
-        println!("{}", object.to_string());
+        println!("{}", object);
     }
 
     #[test]
@@ -341,6 +464,40 @@ mod tests {
             })
         );
 
-        println!("{}", object.to_string());
+        println!("{}", object);
+    }
+
+    #[test]
+    fn object_json() {
+        use crate::object;
+        let object = object!(
+            ("Test", "hello"),
+            ("Test2", true),
+            ("Child", { ("Points", vec!["120.510", "test"]) })
+        );
+
+        assert_eq!(
+            serde_json::to_string(&object).unwrap(),
+            r#"{"Test":"hello","Test2":true,"Child":{"Points":["120.510","test"]}}"#
+        );
+    }
+
+    #[test]
+    fn object_typed_values() {
+        use super::Value;
+        use crate::object;
+
+        let object = object!(
+            ("Id", 1939u64),
+            ("Rarity", 0.1f32),
+            ("Kind", Value::Enum("Hat".to_string())),
+            ("Contact name", None::<String>),
+            ("Creation date", roblox_api::DateTime::from_ymd(2006, 2, 27)),
+        );
+
+        assert_eq!(
+            serde_json::to_string(&object).unwrap(),
+            r#"{"Id":1939,"Rarity":0.1,"Kind":"Hat","Contact name":null,"Creation date":"2006-02-27T00:00:00+00:00"}"#
+        );
     }
 }
@@ -10,28 +10,25 @@ impl Badge {
             Some(creator) => Value::from(object!(
                 ("Id", creator.id),
                 ("Name", creator.name),
-                ("Kind", creator.kind.to_string())
+                ("Kind", Value::Enum(creator.kind.to_string()))
             )),
 
-            None => Value::from("None"),
+            None => Value::Null,
         };
 
         let awarder = match badge.awarder {
             Some(awarder) => Value::from(object!(
                 ("Id", awarder.id),
-                ("Kind", awarder.kind.to_string())
+                ("Kind", Value::Enum(awarder.kind.to_string()))
             )),
 
-            None => Value::from("None"),
+            None => Value::Null,
         };
 
         let statistics = Value::from(object!(
-            ("Rewarded today", badge.statistics.awarded_today.to_string()),
-            (
-                "Rewarded in total",
-                badge.statistics.awarded_total.to_string()
-            ),
-            ("Rarity", badge.statistics.win_rate_percentage.to_string()),
+            ("Rewarded today", badge.statistics.awarded_today),
+            ("Rewarded in total", badge.statistics.awarded_total),
+            ("Rarity", badge.statistics.win_rate_percentage),
         ));
 
         let universe = match badge.universe {
@@ -41,7 +38,7 @@ impl Badge {
                 ("Root place Id", universe.root_place_id)
             )),
 
-            None => Value::from("None"),
+            None => Value::Null,
         };
 
         object!(
@@ -50,12 +47,12 @@ impl Badge {
                 ("Name", badge.name.to_owned()),
                 ("Display name", badge.display_name.to_owned()),
 
-                ("Achievable", badge.enabled.to_string()),
+                ("Achievable", badge.enabled),
 
                 ("Icon image Id", badge.icon_image_id),
 
-                ("Creation date", badge.created.to_string()),
-                ("Last updated", badge.updated.to_string()),
+                ("Creation date", badge.created),
+                ("Last updated", badge.updated),
 
                 ("Description", badge.description.to_owned(), FieldStyle::Description),
 
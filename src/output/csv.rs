@@ -0,0 +1,141 @@
+use crate::object::{Object, Value};
+
+use super::entries;
+
+/// Renders the first list of `object` as one row per entry, or `object` itself as a single row
+/// when it has no list
+pub(super) fn render(object: &Object, columns: &[String], delimiter: char) -> String {
+    render_page(object, &mut Vec::new(), columns, delimiter)
+}
+
+/// Like `render`, but the header row is only written if `headers` is still empty, later pages
+/// reuse the columns of the first one
+pub(super) fn render_page(
+    object: &Object,
+    headers: &mut Vec<String>,
+    columns: &[String],
+    delimiter: char,
+) -> String {
+    let list = object.fields().iter().find_map(|field| match &field.value {
+        Value::Vector(values) => entries(values),
+        _ => None,
+    });
+
+    let rows: Vec<Vec<(String, &Value)>> = match list {
+        Some(entries) => entries.iter().map(|entry| entry.flatten()).collect(),
+        None => vec![object.flatten()],
+    };
+
+    let mut s = String::new();
+    if headers.is_empty() {
+        *headers = super::headers(&rows, columns);
+        if headers.is_empty() {
+            return s;
+        }
+
+        push_record(&mut s, headers.iter().map(String::as_str), delimiter);
+    }
+
+    for row in &rows {
+        let cells: Vec<String> = headers
+            .iter()
+            .map(|header| match row.iter().find(|(key, _)| key == header) {
+                Some((_, value)) => cell(value),
+                None => String::new(),
+            })
+            .collect();
+
+        push_record(&mut s, cells.iter().map(String::as_str), delimiter);
+    }
+
+    s
+}
+
+fn push_record<'a>(s: &mut String, cells: impl Iterator<Item = &'a str>, delimiter: char) {
+    let record = cells
+        .map(|cell| escape(cell, delimiter))
+        .collect::<Vec<_>>()
+        .join(&delimiter.to_string());
+
+    s.push_str(&record);
+    s.push('\n');
+}
+
+/// Quotes a cell as described in RFC 4180, tab separated cells can't be quoted so the special
+/// characters are backslash escaped instead
+fn escape(cell: &str, delimiter: char) -> String {
+    if delimiter == '\t' {
+        return cell
+            .replace('\\', "\\\\")
+            .replace('\t', "\\t")
+            .replace('\r', "\\r")
+            .replace('\n', "\\n");
+    }
+
+    if cell.contains([delimiter, '"', '\r', '\n']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+/// The raw, uncolored representation of `value`, nested lists of objects are written as JSON
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Timestamp(value) => value.to_rfc3339(),
+        Value::Object(_) => serde_json::to_string(value).unwrap_or_default(),
+        Value::Vector(values) => match entries(values) {
+            Some(entries) if !entries.is_empty() => {
+                serde_json::to_string(value).unwrap_or_default()
+            }
+            _ => values.iter().map(cell).collect::<Vec<_>>().join(";"),
+        },
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render, render_page};
+    use crate::object;
+
+    #[test]
+    fn csv_quoting() {
+        let object = object!(
+            ("Next cursor", "abc"),
+            (
+                "Games",
+                vec![
+                    object!(("Creation", {
+                        ("Id", 1u64),
+                        ("Root place", { ("Visits", 10u64), ("About", "fun, \"fast\"\nand free") })
+                    })),
+                    object!(("Creation", { ("Id", 2u64), ("Root place", { ("Visits", 0u64) }) })),
+                ]
+            )
+        );
+
+        assert_eq!(
+            render(&object, &[], ','),
+            "Id,Root place.Visits,Root place.About\n1,10,\"fun, \"\"fast\"\"\nand free\"\n2,0,\n"
+        );
+
+        assert_eq!(
+            render(&object, &["about".to_string(), "id".to_string()], '\t'),
+            "Root place.About\tId\nfun, \"fast\"\\nand free\t1\n\t2\n"
+        );
+    }
+
+    #[test]
+    fn csv_pages_share_header() {
+        let page = |id: u64| object!(("Names", vec![object!(("Id", id), ("Name", "a,b"))]));
+
+        let mut headers = Vec::new();
+        assert_eq!(
+            render_page(&page(1), &mut headers, &[], ','),
+            "Id,Name\n1,\"a,b\"\n"
+        );
+        assert_eq!(render_page(&page(2), &mut headers, &[], ','), "2,\"a,b\"\n");
+    }
+}
@@ -0,0 +1,217 @@
+use std::sync::OnceLock;
+
+use clap::ValueEnum;
+
+use crate::object::{Field, Object, ObjectBuilder, Value};
+
+mod csv;
+mod query;
+mod table;
+
+pub(crate) use query::{Path, Predicate};
+
+static OPTIONS: OnceLock<Options> = OnceLock::new();
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub(crate) enum Format {
+    /// Colored human readable output
+    #[default]
+    Pretty,
+    /// One JSON document per printed object
+    Json,
+    /// Aligned columns, lists get one row per entry
+    Table,
+    /// Comma separated values with a header row, nested keys are joined with dots
+    Csv,
+    /// Tab separated values with a header row, nested keys are joined with dots
+    Tsv,
+}
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Options {
+    pub(crate) format: Format,
+    /// Column names (or their last path segment) to keep in tabular formats, all when empty
+    pub(crate) columns: Vec<String>,
+    /// Paths to project printed objects onto, everything when empty
+    pub(crate) select: Vec<Path>,
+    /// Predicates that printed objects or their list entries have to match
+    pub(crate) filter: Vec<Predicate>,
+}
+
+/// Sets the options used by `print`, only the first call has an effect
+pub(crate) fn init(options: Options) {
+    let _ = OPTIONS.set(options);
+}
+
+pub(crate) fn options() -> &'static Options {
+    OPTIONS.get_or_init(Options::default)
+}
+
+pub(crate) fn print(object: &Object) {
+    if let Some(object) = prepare(object) {
+        write(&object);
+    }
+}
+
+/// Prints a listing which arrives in pages, each page is printed as soon as it is pushed except
+/// for tables, which need every row to know the column widths
+#[derive(Debug, Default)]
+pub(crate) struct Stream {
+    pages: Vec<Object>,
+    headers: Vec<String>,
+}
+
+impl Stream {
+    pub(crate) fn push(&mut self, page: &Object) {
+        let Some(page) = prepare(page) else {
+            return;
+        };
+
+        let options = options();
+        match options.format {
+            Format::Table => self.pages.push(page),
+            Format::Csv => print!(
+                "{}",
+                csv::render_page(&page, &mut self.headers, &options.columns, ',')
+            ),
+            Format::Tsv => print!(
+                "{}",
+                csv::render_page(&page, &mut self.headers, &options.columns, '\t')
+            ),
+            _ => write(&page),
+        }
+    }
+
+    /// Prints the buffered pages as one object, lists are concatenated and the other fields are
+    /// taken from the last page
+    pub(crate) fn finish(self) {
+        let mut pages = self.pages.into_iter();
+        let Some(first) = pages.next() else {
+            return;
+        };
+
+        let mut fields = first.fields().to_vec();
+        for page in pages {
+            for field in page.fields() {
+                match fields.iter_mut().find(|x| x.key == field.key) {
+                    Some(Field {
+                        value: Value::Vector(values),
+                        ..
+                    }) => {
+                        if let Value::Vector(page_values) = &field.value {
+                            values.extend(page_values.iter().cloned());
+                        }
+                    }
+                    Some(existing) => *existing = field.clone(),
+                    None => fields.push(field.clone()),
+                }
+            }
+        }
+
+        let object = fields
+            .into_iter()
+            .fold(ObjectBuilder::default(), |builder, field| {
+                builder.with_field(field)
+            })
+            .build();
+
+        write(&object);
+    }
+}
+
+/// Applies the `--where` and `--select` options, `None` if the object was filtered out
+fn prepare(object: &Object) -> Option<Object> {
+    let options = options();
+    let object = query::filter(object, &options.filter)?;
+
+    if options.select.is_empty() {
+        Some(object)
+    } else {
+        Some(query::select(&object, &options.select))
+    }
+}
+
+fn write(object: &Object) {
+    let options = options();
+    match options.format {
+        Format::Pretty => print!("{}", object),
+        Format::Json => println!(
+            "{}",
+            serde_json::to_string(object).expect("error: failed to serialize object")
+        ),
+        Format::Table => print!("{}", table::render(object, &options.columns)),
+        Format::Csv => print!("{}", csv::render(object, &options.columns, ',')),
+        Format::Tsv => print!("{}", csv::render(object, &options.columns, '\t')),
+    }
+}
+
+/// The entries of a list field, if all of them are objects.
+///
+/// Entries wrapped in a single field of the same name, like `("Gamepass", { .. })`, are unwrapped
+fn entries(values: &[Value]) -> Option<Vec<&Object>> {
+    let objects = values
+        .iter()
+        .map(|value| match value {
+            Value::Object(object) => Some(object),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    fn wrapper(object: &Object) -> Option<(&str, &Object)> {
+        match object.fields() {
+            [field] => match &field.value {
+                Value::Object(inner) => Some((field.key.as_str(), inner)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    let key = objects
+        .first()
+        .and_then(|object| wrapper(object))
+        .map(|(key, _)| key);
+    let unwrapped = objects
+        .iter()
+        .map(|object| wrapper(object).filter(|(inner_key, _)| Some(*inner_key) == key))
+        .collect::<Option<Vec<_>>>();
+
+    match unwrapped {
+        Some(unwrapped) => Some(unwrapped.into_iter().map(|(_, inner)| inner).collect()),
+        None => Some(objects),
+    }
+}
+
+/// Whether the column `key`, a dotted path, was requested by `column`
+fn column_matches(key: &str, column: &str) -> bool {
+    key.eq_ignore_ascii_case(column)
+        || key
+            .rsplit('.')
+            .next()
+            .is_some_and(|last| last.eq_ignore_ascii_case(column))
+}
+
+/// Collects the union of the keys of `rows` in order of appearance, narrowed down to `columns`
+fn headers(rows: &[Vec<(String, &Value)>], columns: &[String]) -> Vec<String> {
+    let mut headers: Vec<String> = Vec::new();
+    for (key, _) in rows.iter().flatten() {
+        if !headers.contains(key) {
+            headers.push(key.to_owned());
+        }
+    }
+
+    if columns.is_empty() {
+        return headers;
+    }
+
+    let mut selected: Vec<String> = Vec::new();
+    for column in columns {
+        for header in &headers {
+            if column_matches(header, column) && !selected.contains(header) {
+                selected.push(header.to_owned());
+            }
+        }
+    }
+
+    selected
+}
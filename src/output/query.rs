@@ -0,0 +1,400 @@
+use std::{cmp::Ordering, str::FromStr};
+
+use chrono::{DateTime, FixedOffset, NaiveDate};
+
+use crate::object::{Field, Object, ObjectBuilder, Value};
+
+use super::entries;
+
+/// A dotted path of field keys such as `Assets[].Asset.Id`, lists are traversed implicitly so the
+/// `[]` suffix is optional
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Path(Vec<String>);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Operator {
+    Equal,
+    NotEqual,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+    Contains,
+}
+
+/// A comparison like `Price > 100` or `Name ~ sword`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Predicate {
+    path: Path,
+    operator: Operator,
+    operand: String,
+}
+
+impl FromStr for Path {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let segments: Vec<String> = s
+            .split('.')
+            .map(|segment| segment.trim().trim_end_matches("[]").trim().to_string())
+            .collect();
+
+        if segments.iter().any(String::is_empty) {
+            return Err(format!("invalid path: `{s}`"));
+        }
+
+        Ok(Self(segments))
+    }
+}
+
+impl FromStr for Predicate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const OPERATORS: &[(&str, Operator)] = &[
+            (">=", Operator::GreaterOrEqual),
+            ("<=", Operator::LessOrEqual),
+            ("!=", Operator::NotEqual),
+            ("==", Operator::Equal),
+            ("=", Operator::Equal),
+            (">", Operator::Greater),
+            ("<", Operator::Less),
+            ("~", Operator::Contains),
+        ];
+
+        let (index, token, operator) = s
+            .char_indices()
+            .find_map(|(index, _)| {
+                OPERATORS
+                    .iter()
+                    .find(|(token, _)| s[index..].starts_with(token))
+                    .map(|(token, operator)| (index, *token, *operator))
+            })
+            .ok_or(format!(
+                "missing operator in `{s}`, expected one of = != > >= < <= ~"
+            ))?;
+
+        let operand = s[index + token.len()..].trim();
+        let operand = operand
+            .strip_prefix(['"', '\''])
+            .and_then(|operand| operand.strip_suffix(['"', '\'']))
+            .unwrap_or(operand);
+
+        Ok(Self {
+            path: s[..index].parse()?,
+            operator,
+            operand: operand.to_string(),
+        })
+    }
+}
+
+/// Applies `predicates` to `object`, a predicate on a field of `object` itself decides whether it
+/// is kept at all, otherwise it filters the entries of the lists that have the field
+pub(crate) fn filter(object: &Object, predicates: &[Predicate]) -> Option<Object> {
+    let mut object = object.clone();
+
+    for predicate in predicates {
+        let segments = predicate.path.0.as_slice();
+
+        let root = resolve(&object, segments, false);
+        if !root.is_empty() {
+            if !root.iter().any(|value| predicate.matches(value)) {
+                return None;
+            }
+
+            continue;
+        }
+
+        let mut builder = ObjectBuilder::default();
+        for field in object.fields() {
+            let Value::Vector(values) = &field.value else {
+                builder = builder.with_field(field.clone());
+                continue;
+            };
+
+            // The path may be prefixed with the key of the list itself
+            let segments = match segments.split_first() {
+                Some((first, rest)) if !rest.is_empty() && key_matches(&field.key, first) => rest,
+                _ => segments,
+            };
+
+            builder = builder.with_field(Field {
+                value: Value::Vector(filter_entries(values, segments, predicate)),
+                ..field.clone()
+            });
+        }
+
+        object = builder.build();
+    }
+
+    Some(object)
+}
+
+fn filter_entries(values: &[Value], segments: &[String], predicate: &Predicate) -> Vec<Value> {
+    let Some(entries) = entries(values) else {
+        return values.to_vec();
+    };
+
+    // Try the unwrapped entry first, then the entry with its wrapper like `Gamepass.Price`
+    let resolved: Vec<Vec<&Value>> = entries
+        .iter()
+        .zip(values)
+        .map(|(entry, value)| {
+            let mut resolved = resolve(entry, segments, true);
+            if resolved.is_empty() {
+                resolve_value(value, segments, true, &mut resolved);
+            }
+
+            resolved
+        })
+        .collect();
+
+    if resolved.iter().all(Vec::is_empty) {
+        return values.to_vec();
+    }
+
+    values
+        .iter()
+        .zip(resolved)
+        .filter(|(_, resolved)| resolved.iter().any(|value| predicate.matches(value)))
+        .map(|(value, _)| value.clone())
+        .collect()
+}
+
+/// Keeps only the fields of `object` on one of `paths`, preserving the structure leading to them
+pub(crate) fn select(object: &Object, paths: &[Path]) -> Object {
+    let paths: Vec<&[String]> = paths.iter().map(|path| path.0.as_slice()).collect();
+    select_paths(object, &paths)
+}
+
+fn select_paths(object: &Object, paths: &[&[String]]) -> Object {
+    let mut builder = ObjectBuilder::default();
+
+    for field in object.fields() {
+        let tails: Vec<&[String]> = paths
+            .iter()
+            .filter_map(|path| match path.split_first() {
+                Some((first, rest)) if key_matches(&field.key, first) => Some(rest),
+                _ => None,
+            })
+            .collect();
+
+        if tails.is_empty() {
+            continue;
+        }
+
+        if tails.iter().any(|tail| tail.is_empty()) {
+            builder = builder.with_field(field.clone());
+            continue;
+        }
+
+        if let Some(value) = select_value(&field.value, &tails) {
+            builder = builder.with_field(Field {
+                value,
+                ..field.clone()
+            });
+        }
+    }
+
+    builder.build()
+}
+
+fn select_value(value: &Value, paths: &[&[String]]) -> Option<Value> {
+    match value {
+        Value::Object(object) => {
+            let object = select_paths(object, paths);
+            (!object.fields().is_empty()).then_some(Value::Object(object))
+        }
+
+        Value::Vector(values) => Some(Value::Vector(
+            values
+                .iter()
+                .filter_map(|value| select_value(value, paths))
+                .collect(),
+        )),
+
+        _ => None,
+    }
+}
+
+fn key_matches(key: &str, segment: &str) -> bool {
+    key.eq_ignore_ascii_case(segment)
+}
+
+/// Every value found at `segments` under `object`, lists are only traversed when `into_lists`
+fn resolve<'a>(object: &'a Object, segments: &[String], into_lists: bool) -> Vec<&'a Value> {
+    let Some((first, rest)) = segments.split_first() else {
+        return Vec::new();
+    };
+
+    let mut values = Vec::new();
+    for field in object.fields() {
+        if key_matches(&field.key, first) {
+            resolve_value(&field.value, rest, into_lists, &mut values);
+        }
+    }
+
+    values
+}
+
+fn resolve_value<'a>(
+    value: &'a Value,
+    segments: &[String],
+    into_lists: bool,
+    values: &mut Vec<&'a Value>,
+) {
+    match value {
+        _ if segments.is_empty() => values.push(value),
+        Value::Object(object) => values.extend(resolve(object, segments, into_lists)),
+        Value::Vector(elements) if into_lists => {
+            for element in elements {
+                resolve_value(element, segments, into_lists, values);
+            }
+        }
+        _ => {}
+    }
+}
+
+impl Predicate {
+    fn matches(&self, value: &Value) -> bool {
+        if let Value::Vector(values) = value {
+            return values.iter().any(|value| self.matches(value));
+        }
+
+        if self.operator == Operator::Contains {
+            return value
+                .to_string()
+                .to_lowercase()
+                .contains(&self.operand.to_lowercase());
+        }
+
+        let ordering = match value {
+            Value::Null => {
+                let null = matches!(self.operand.to_lowercase().as_str(), "null" | "none" | "");
+                return match self.operator {
+                    Operator::Equal => null,
+                    Operator::NotEqual => !null,
+                    _ => false,
+                };
+            }
+
+            Value::Bool(value) => {
+                let operand = match self.operand.to_lowercase().as_str() {
+                    "true" | "yes" | "1" => true,
+                    "false" | "no" | "0" => false,
+                    _ => return self.operator == Operator::NotEqual,
+                };
+
+                value.cmp(&operand)
+            }
+
+            Value::Integer(value) => match self.number_ordering(*value as f64) {
+                Some(ordering) => ordering,
+                None => return self.operator == Operator::NotEqual,
+            },
+
+            Value::Float(value) => match self.number_ordering(*value) {
+                Some(ordering) => ordering,
+                None => return self.operator == Operator::NotEqual,
+            },
+
+            Value::Timestamp(value) => match timestamp(&self.operand) {
+                Some(operand) => value.cmp(&operand),
+                None => return self.operator == Operator::NotEqual,
+            },
+
+            Value::String(value) | Value::Enum(value) => {
+                value.to_lowercase().cmp(&self.operand.to_lowercase())
+            }
+
+            Value::Object(_) | Value::Vector(_) => return false,
+        };
+
+        match self.operator {
+            Operator::Equal => ordering == Ordering::Equal,
+            Operator::NotEqual => ordering != Ordering::Equal,
+            Operator::Greater => ordering == Ordering::Greater,
+            Operator::GreaterOrEqual => ordering != Ordering::Less,
+            Operator::Less => ordering == Ordering::Less,
+            Operator::LessOrEqual => ordering != Ordering::Greater,
+            Operator::Contains => unreachable!(),
+        }
+    }
+
+    fn number_ordering(&self, value: f64) -> Option<Ordering> {
+        value.partial_cmp(&self.operand.parse::<f64>().ok()?)
+    }
+}
+
+/// Parses either a full RFC 3339 timestamp or a `YYYY-MM-DD` date at midnight UTC
+fn timestamp(s: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(s).ok().or_else(|| {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .ok()?
+            .and_hms_opt(0, 0, 0)
+            .map(|date| date.and_utc().fixed_offset())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Path, Predicate, filter, select};
+    use crate::object;
+
+    fn gamepasses() -> crate::object::Object {
+        object!(
+            ("Next cursor", "abc"),
+            (
+                "Gamepasses",
+                vec![
+                    object!(("Gamepass", { ("Id", 1u64), ("Price", 50u64), ("On sale", true) })),
+                    object!(("Gamepass", { ("Id", 2u64), ("Price", 150u64), ("On sale", true) })),
+                    object!(("Gamepass", { ("Id", 3u64), ("Price", 500u64), ("On sale", false) })),
+                ]
+            )
+        )
+    }
+
+    #[test]
+    fn predicate_parsing() {
+        assert!("Price".parse::<Predicate>().is_err());
+        assert!(" > 5".parse::<Predicate>().is_err());
+        assert_eq!(
+            "Name = 'Sword'".parse::<Predicate>(),
+            "Name=Sword".parse::<Predicate>()
+        );
+    }
+
+    #[test]
+    fn filter_entries() {
+        let predicates: Vec<Predicate> = vec!["Price > 100".parse().unwrap()];
+        let filtered = filter(&gamepasses(), &predicates).unwrap();
+        let expected = object!(
+            ("Next cursor", "abc"),
+            (
+                "Gamepasses",
+                vec![
+                    object!(("Gamepass", { ("Id", 2u64), ("Price", 150u64), ("On sale", true) })),
+                    object!(("Gamepass", { ("Id", 3u64), ("Price", 500u64), ("On sale", false) })),
+                ]
+            )
+        );
+        assert_eq!(filtered, expected);
+
+        let predicates: Vec<Predicate> = vec![
+            "Gamepasses.Gamepass.On sale = yes".parse().unwrap(),
+            "Price <= 100".parse().unwrap(),
+        ];
+        let filtered = filter(&gamepasses(), &predicates).unwrap();
+        assert_eq!(
+            select(
+                &filtered,
+                &["Gamepasses[].Gamepass.Id".parse::<Path>().unwrap()]
+            ),
+            object!(("Gamepasses", vec![object!(("Gamepass", { ("Id", 1u64) }))]))
+        );
+
+        let predicates: Vec<Predicate> = vec!["Next cursor != abc".parse().unwrap()];
+        assert_eq!(filter(&gamepasses(), &predicates), None);
+    }
+}
@@ -0,0 +1,230 @@
+use std::fmt::Write;
+
+use console::{Alignment, Term, pad_str, style, truncate_str};
+
+use crate::{
+    conclusion::Conclusion,
+    object::{Object, Value},
+};
+
+use super::{column_matches, entries, headers};
+
+const SEPARATOR: &str = "  ";
+const MIN_COLUMN_WIDTH: usize = 4;
+
+/// Renders the scalar fields of `object` as a key-value table, followed by one table per list
+pub(super) fn render(object: &Object, columns: &[String]) -> String {
+    let width = Term::stdout()
+        .size_checked()
+        .map(|(_, width)| width as usize)
+        .unwrap_or(usize::MAX);
+
+    let mut s = String::new();
+
+    let details: Vec<(String, &Value)> = object
+        .flatten()
+        .into_iter()
+        .filter(|(key, value)| {
+            list(value).is_none()
+                && (columns.is_empty() || columns.iter().any(|column| column_matches(key, column)))
+        })
+        .collect();
+
+    if !details.is_empty() {
+        let key_width = details
+            .iter()
+            .map(|(key, _)| console::measure_text_width(key))
+            .max()
+            .unwrap_or_default();
+
+        for (key, value) in &details {
+            let value_width = width.saturating_sub(key_width + SEPARATOR.len());
+            let _ = writeln!(
+                s,
+                "{}{SEPARATOR}{}",
+                style(pad_str(key, key_width, Alignment::Left, None)).bold(),
+                truncate_str(&cell(value), value_width.max(MIN_COLUMN_WIDTH), "…")
+            );
+        }
+    }
+
+    for field in object.fields() {
+        let Some(entries) = list(&field.value) else {
+            continue;
+        };
+
+        if !s.is_empty() {
+            s.push('\n');
+        }
+
+        let _ = writeln!(s, "{}", style(&field.key).magenta().bold());
+        if entries.is_empty() {
+            let _ = writeln!(s, "{}", style("no entries").dim());
+            continue;
+        }
+
+        let rows: Vec<Vec<(String, &Value)>> =
+            entries.iter().map(|entry| entry.flatten()).collect();
+        render_rows(&mut s, &rows, columns, width);
+    }
+
+    s
+}
+
+/// The entries of `value` if it should be rendered as a table
+fn list(value: &Value) -> Option<Vec<&Object>> {
+    match value {
+        Value::Vector(values) => entries(values),
+        _ => None,
+    }
+}
+
+fn render_rows(s: &mut String, rows: &[Vec<(String, &Value)>], columns: &[String], width: usize) {
+    let headers = headers(rows, columns);
+    if headers.is_empty() {
+        return;
+    }
+
+    let cells: Vec<Vec<(String, Alignment)>> = rows
+        .iter()
+        .map(|row| {
+            headers
+                .iter()
+                .map(|header| match row.iter().find(|(key, _)| key == header) {
+                    Some((_, value)) => (cell(value), alignment(value)),
+                    None => (String::new(), Alignment::Left),
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut widths: Vec<usize> = headers
+        .iter()
+        .map(|header| console::measure_text_width(header))
+        .collect();
+
+    for row in &cells {
+        for (width, (text, _)) in widths.iter_mut().zip(row) {
+            *width = (*width).max(console::measure_text_width(text));
+        }
+    }
+
+    fit(&mut widths, width);
+
+    let header = headers
+        .iter()
+        .zip(&widths)
+        .enumerate()
+        .map(|(index, (header, width))| {
+            let header = truncate_str(header, *width, "…");
+            let mut header = pad_str(&header, *width, Alignment::Left, None).into_owned();
+            // Trimmed like the rows, before styling hides the padding behind escape codes
+            if index + 1 == headers.len() {
+                header.truncate(header.trim_end().len());
+            }
+
+            style(header).bold().underlined().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join(SEPARATOR);
+    let _ = writeln!(s, "{}", header);
+
+    for row in &cells {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|((text, alignment), width)| {
+                let text = truncate_str(text, *width, "…");
+                pad_str(&text, *width, *alignment, None).into_owned()
+            })
+            .collect::<Vec<_>>()
+            .join(SEPARATOR);
+        let _ = writeln!(s, "{}", line.trim_end());
+    }
+}
+
+/// Shrinks the widest columns until the row fits into `width`
+fn fit(widths: &mut [usize], width: usize) {
+    let separators = SEPARATOR.len() * widths.len().saturating_sub(1);
+
+    while widths.iter().sum::<usize>() + separators > width {
+        let Some((index, widest)) = widths
+            .iter()
+            .copied()
+            .enumerate()
+            .max_by_key(|(_, width)| *width)
+        else {
+            break;
+        };
+
+        if widest <= MIN_COLUMN_WIDTH {
+            break;
+        }
+
+        widths[index] -= 1;
+    }
+}
+
+fn alignment(value: &Value) -> Alignment {
+    match value {
+        Value::Integer(_) | Value::Float(_) => Alignment::Right,
+        _ => Alignment::Left,
+    }
+}
+
+/// The single line representation of `value` inside a table cell
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Bool(value) => Conclusion(*value).value().to_string(),
+        Value::Object(object) => format!("{{{} fields}}", object.fields().len()),
+        Value::Vector(values) => match entries(values) {
+            Some(entries) if !entries.is_empty() => format!("[{} entries]", entries.len()),
+            _ => values.iter().map(cell).collect::<Vec<_>>().join(", "),
+        },
+        value => value.to_string().replace(['\r', '\n'], " "),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fit, render};
+    use crate::object;
+
+    #[test]
+    fn render_list() {
+        console::set_colors_enabled(false);
+
+        let object = object!(
+            ("Next cursor", "abc"),
+            (
+                "Gamepasses",
+                vec![
+                    object!(("Gamepass", { ("Id", 1u64), ("Name", "VIP"), ("Owned", true) })),
+                    object!(("Gamepass", { ("Id", 20u64), ("Name", "Speed"), ("Owned", false) })),
+                ]
+            )
+        );
+
+        assert_eq!(
+            render(&object, &[]),
+            "Next cursor  abc\n\nGamepasses\nId  Name   Owned\n 1  VIP    Yes\n20  Speed  No\n"
+        );
+
+        assert_eq!(
+            render(&object, &["name".to_string()]),
+            "Gamepasses\nName\nVIP\nSpeed\n"
+        );
+    }
+
+    #[test]
+    fn fit_shrinks_widest_column() {
+        let mut widths = vec![10, 40, 6];
+        fit(&mut widths, 40);
+        assert_eq!(widths, vec![10, 20, 6]);
+
+        let mut widths = vec![5, 5];
+        fit(&mut widths, 3);
+        assert_eq!(widths, vec![4, 4]);
+    }
+}
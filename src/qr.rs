@@ -0,0 +1,62 @@
+//! QR codes drawn in the terminal, the encoding itself is left to the `qrcode` crate
+
+use qrcode::{Color, EcLevel};
+
+/// What the spec asks for, scanners struggle to find the code with less on dark themes
+const QUIET_ZONE: usize = 4;
+
+pub(crate) struct QrCode(qrcode::QrCode);
+
+impl QrCode {
+    /// Encodes `data` in the smallest version that fits it, `None` if it's too long
+    pub(crate) fn encode(data: &[u8]) -> Option<Self> {
+        qrcode::QrCode::with_error_correction_level(data, EcLevel::L)
+            .ok()
+            .map(Self)
+    }
+
+    /// Draws the code with half blocks, two rows per line, with a quiet zone around it
+    pub(crate) fn render(&self) -> Vec<String> {
+        let width = self.0.width();
+        let size = width + QUIET_ZONE * 2;
+        let light = |x: usize, y: usize| {
+            let (Some(x), Some(y)) = (x.checked_sub(QUIET_ZONE), y.checked_sub(QUIET_ZONE)) else {
+                return true;
+            };
+
+            x >= width || y >= width || self.0[(x, y)] == Color::Light
+        };
+
+        (0..size)
+            .step_by(2)
+            .map(|y| {
+                (0..size)
+                    .map(|x| match (light(x, y), y + 1 >= size || light(x, y + 1)) {
+                        (true, true) => '█',
+                        (true, false) => '▀',
+                        (false, true) => '▄',
+                        (false, false) => ' ',
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QrCode;
+
+    #[test]
+    fn render_quiet_zone() {
+        let lines = QrCode::encode(b"https://www.roblox.com").unwrap().render();
+
+        // Version 2 is 25 modules wide, with 4 light modules on each side
+        assert_eq!(lines.len(), 17);
+        assert!(lines.iter().all(|line| line.chars().count() == 33));
+        assert!(lines[..2].iter().all(|line| line.chars().all(|x| x == '█')));
+
+        // The top left finder pattern starts right after the quiet zone
+        assert!(lines[2].starts_with("████ ▄▄▄▄▄ █"));
+    }
+}
@@ -0,0 +1,267 @@
+use std::{collections::BTreeMap, env, fs, io::Write, path::PathBuf, sync::OnceLock};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use console::Term;
+use openssl::{
+    hash::MessageDigest,
+    pkcs5, rand,
+    symm::{self, Cipher},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Context, Error, Result};
+
+/// Read instead of asking for the passphrase, for when there is no terminal
+pub(crate) const PASSPHRASE_VAR: &str = "RBX_PASSPHRASE";
+
+const ITERATIONS: usize = 600_000;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+
+/// Encrypted into the file so a wrong passphrase is caught before anything is read or written
+const CHECK: &[u8] = b"rbx";
+
+/// The key derived from the passphrase, so it's only asked for once per run
+static KEY: OnceLock<[u8; 32]> = OnceLock::new();
+
+/// Every secret is encrypted on its own with AES-256-GCM, keyed by PBKDF2 over the passphrase
+#[derive(Deserialize, Serialize)]
+struct Secrets {
+    salt: String,
+    check: String,
+    secrets: BTreeMap<String, String>,
+}
+
+fn path() -> Result<PathBuf> {
+    let path = confy::get_configuration_file_path(env!("CARGO_BIN_NAME"), Some("config"))
+        .context("failed to find the config directory")?;
+
+    Ok(path.with_file_name("secrets.json"))
+}
+
+fn corrupted() -> Error {
+    Error::Config("the secrets file is corrupted".to_string())
+}
+
+fn read() -> Result<Option<Secrets>> {
+    let path = path()?;
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(path).context("failed to read the secrets file")?;
+    serde_json::from_str(&content).map_err(|_| corrupted())
+}
+
+/// Writes a new file next to the old one and renames it over it, so a crash or a full disk never
+/// leaves a truncated file behind
+fn write(secrets: &Secrets) -> Result<()> {
+    let content = serde_json::to_string_pretty(secrets).map_err(|_| corrupted())?;
+
+    let path = path()?;
+    let mut suffix = [0; 8];
+    rand::rand_bytes(&mut suffix).map_err(crypto_error)?;
+    let temporary = path.with_extension(format!("json.{:x}.tmp", u64::from_ne_bytes(suffix)));
+
+    let mut options = fs::File::options();
+    options.create_new(true).write(true);
+
+    #[cfg(target_family = "unix")]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let result = options
+        .open(&temporary)
+        .context("failed to open the secrets file")
+        .and_then(|mut file| {
+            file.write_all(content.as_bytes())
+                .and_then(|_| file.sync_all())
+                .context("failed to write the secrets file")
+        })
+        .and_then(|_| fs::rename(&temporary, &path).context("failed to replace the secrets file"));
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temporary);
+    }
+
+    result
+}
+
+fn passphrase(confirm: bool) -> Result<String> {
+    if let Ok(passphrase) = env::var(PASSPHRASE_VAR) {
+        return Ok(passphrase);
+    }
+
+    let term = Term::stderr();
+    if !term.is_term() {
+        return Err(Error::Config(format!(
+            "set {PASSPHRASE_VAR} to unlock the secrets file without a terminal"
+        )));
+    }
+
+    term.write_str("Passphrase: ")
+        .context("failed to write to stderr")?;
+    let passphrase = term
+        .read_secure_line()
+        .context("failed to read the passphrase")?;
+
+    if confirm {
+        if passphrase.is_empty() {
+            return Err(Error::Config("the passphrase can't be empty".to_string()));
+        }
+
+        term.write_str("Repeat passphrase: ")
+            .context("failed to write to stderr")?;
+        if term
+            .read_secure_line()
+            .context("failed to read the passphrase")?
+            != passphrase
+        {
+            return Err(Error::Config("the passphrases don't match".to_string()));
+        }
+    }
+
+    Ok(passphrase)
+}
+
+fn crypto_error(error: openssl::error::ErrorStack) -> Error {
+    Error::Io(format!("failed to encrypt the secrets file: {error}"))
+}
+
+fn derive(passphrase: &str, salt: &[u8]) -> Result<[u8; 32]> {
+    let mut key = [0; 32];
+    pkcs5::pbkdf2_hmac(
+        passphrase.as_bytes(),
+        salt,
+        ITERATIONS,
+        MessageDigest::sha256(),
+        &mut key,
+    )
+    .map_err(crypto_error)?;
+
+    Ok(key)
+}
+
+fn encrypt(key: &[u8; 32], plaintext: &[u8]) -> Result<String> {
+    let mut nonce = [0; NONCE_LEN];
+    rand::rand_bytes(&mut nonce).map_err(crypto_error)?;
+
+    let mut tag = [0; TAG_LEN];
+    let ciphertext = symm::encrypt_aead(
+        Cipher::aes_256_gcm(),
+        key,
+        Some(&nonce),
+        &[],
+        plaintext,
+        &mut tag,
+    )
+    .map_err(crypto_error)?;
+
+    Ok(STANDARD.encode([&nonce[..], &ciphertext, &tag].concat()))
+}
+
+fn decrypt(key: &[u8; 32], data: &str) -> Result<Vec<u8>> {
+    let data = STANDARD.decode(data).map_err(|_| corrupted())?;
+    if data.len() < NONCE_LEN + TAG_LEN {
+        return Err(corrupted());
+    }
+
+    let (nonce, data) = data.split_at(NONCE_LEN);
+    let (ciphertext, tag) = data.split_at(data.len() - TAG_LEN);
+
+    symm::decrypt_aead(
+        Cipher::aes_256_gcm(),
+        key,
+        Some(nonce),
+        &[],
+        ciphertext,
+        tag,
+    )
+    .map_err(|_| Error::Auth("wrong passphrase for the secrets file".to_string()))
+}
+
+/// The key of an existing file, checked against the file before it's used
+fn unlock(secrets: &Secrets) -> Result<[u8; 32]> {
+    if let Some(key) = KEY.get() {
+        return Ok(*key);
+    }
+
+    let salt = STANDARD.decode(&secrets.salt).map_err(|_| corrupted())?;
+    let key = derive(&passphrase(false)?, &salt)?;
+    decrypt(&key, &secrets.check)?;
+
+    Ok(*KEY.get_or_init(|| key))
+}
+
+fn create() -> Result<(Secrets, [u8; 32])> {
+    let mut salt = [0; SALT_LEN];
+    rand::rand_bytes(&mut salt).map_err(crypto_error)?;
+
+    let key = derive(&passphrase(true)?, &salt)?;
+    let secrets = Secrets {
+        salt: STANDARD.encode(salt),
+        check: encrypt(&key, CHECK)?,
+        secrets: BTreeMap::new(),
+    };
+
+    Ok((secrets, *KEY.get_or_init(|| key)))
+}
+
+pub(super) fn lookup(id: &str) -> Result<Option<String>> {
+    let Some(secrets) = read()? else {
+        return Ok(None);
+    };
+
+    let Some(data) = secrets.secrets.get(id) else {
+        return Ok(None);
+    };
+
+    let secret = decrypt(&unlock(&secrets)?, data)?;
+    String::from_utf8(secret).map(Some).map_err(|_| corrupted())
+}
+
+pub(super) fn store(id: &str, secret: &str) -> Result<()> {
+    let (mut secrets, key) = match read()? {
+        Some(secrets) => {
+            let key = unlock(&secrets)?;
+            (secrets, key)
+        }
+
+        None => create()?,
+    };
+
+    secrets
+        .secrets
+        .insert(id.to_owned(), encrypt(&key, secret.as_bytes())?);
+    write(&secrets)
+}
+
+pub(super) fn delete(id: &str) -> Result<()> {
+    // Removing an entry doesn't need the passphrase, the others stay encrypted as they are
+    if let Some(mut secrets) = read()?
+        && secrets.secrets.remove(id).is_some()
+    {
+        write(&secrets)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decrypt, derive, encrypt};
+
+    #[test]
+    fn encrypt_round_trip() {
+        let key = derive("hunter2", b"0123456789abcdef").unwrap();
+        let data = encrypt(&key, b"_|WARNING:-DO-NOT-SHARE-THIS").unwrap();
+
+        assert_eq!(
+            decrypt(&key, &data).unwrap(),
+            b"_|WARNING:-DO-NOT-SHARE-THIS"
+        );
+
+        let other = derive("hunter3", b"0123456789abcdef").unwrap();
+        assert_eq!(decrypt(&other, &data).unwrap_err().exit_code(), 3);
+    }
+}
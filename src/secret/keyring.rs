@@ -0,0 +1,89 @@
+use std::{
+    env,
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use crate::error::{Context, Error, Result};
+
+/// `secret-tool` ships with libsecret and talks to whichever Secret Service is running
+const SECRET_TOOL: &str = "secret-tool";
+
+/// Whether a Secret Service answers on the session bus, a search fails without one even when
+/// `secret-tool` and the bus are there
+pub(crate) fn available() -> bool {
+    env::var_os("DBUS_SESSION_BUS_ADDRESS").is_some()
+        && Command::new(SECRET_TOOL)
+            .args(["search", "service", env!("CARGO_BIN_NAME")])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+}
+
+fn attributes(key: &str) -> [&str; 4] {
+    ["service", env!("CARGO_BIN_NAME"), "key", key]
+}
+
+pub(super) fn lookup(key: &str) -> Result<Option<String>> {
+    find(&attributes(key))
+}
+
+/// The secret matching every attribute pair in `attributes`, also used to read what other
+/// programs keep in the keyring
+pub(crate) fn find(attributes: &[&str]) -> Result<Option<String>> {
+    let output = Command::new(SECRET_TOOL)
+        .arg("lookup")
+        .args(attributes)
+        .output()
+        .context("failed to run secret-tool")?;
+
+    // A missing secret is reported with a failing exit code and nothing on stdout
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    let secret = String::from_utf8_lossy(&output.stdout);
+    Ok(Some(secret.trim_end().to_owned()))
+}
+
+pub(super) fn store(key: &str, label: &str, secret: &str) -> Result<()> {
+    // The secret is read from stdin so it never shows up in the process list
+    let mut child = Command::new(SECRET_TOOL)
+        .args(["store", "--label", label])
+        .args(attributes(key))
+        .stdin(Stdio::piped())
+        .spawn()
+        .context("failed to run secret-tool")?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(secret.as_bytes())
+            .context("failed to write to secret-tool")?;
+    }
+
+    let status = child.wait().context("failed to run secret-tool")?;
+    if !status.success() {
+        return Err(Error::Config(
+            "failed to store the secret in the keyring".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+pub(super) fn delete(key: &str) -> Result<()> {
+    let status = Command::new(SECRET_TOOL)
+        .arg("clear")
+        .args(attributes(key))
+        .status()
+        .context("failed to run secret-tool")?;
+
+    if !status.success() {
+        return Err(Error::Config(
+            "failed to delete the secret from the keyring".to_string(),
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,146 @@
+mod file;
+pub(crate) mod keyring;
+pub(crate) mod totp;
+
+use std::fmt;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::Account,
+    error::{Error, Result},
+};
+
+/// Where new cookies are stored, picked with `account migrate`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Backend {
+    /// Written into the config as is, only used when picked
+    Plain,
+    /// The Secret Service keyring, through `secret-tool`
+    Keyring,
+    /// A file next to the config, encrypted with a passphrase
+    File,
+}
+
+impl Backend {
+    /// The keyring when there is a Secret Service to reach, the encrypted file otherwise
+    pub(crate) fn preferred() -> Self {
+        match keyring::available() {
+            true => Self::Keyring,
+            false => Self::File,
+        }
+    }
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Plain => write!(f, "plain"),
+            Self::Keyring => write!(f, "keyring"),
+            Self::File => write!(f, "file"),
+        }
+    }
+}
+
+/// What the config keeps instead of the cookie, the key the cookie is stored under
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "backend", content = "key", rename_all = "lowercase")]
+pub(crate) enum Reference {
+    Keyring(String),
+    File(String),
+}
+
+impl Reference {
+    fn backend(reference: &Option<Self>) -> Backend {
+        match reference {
+            Some(Self::Keyring(_)) => Backend::Keyring,
+            Some(Self::File(_)) => Backend::File,
+            None => Backend::Plain,
+        }
+    }
+}
+
+/// The backend the cookie of `account` is kept in
+pub(crate) fn backend(account: &Account) -> Backend {
+    Reference::backend(&account.secret)
+}
+
+/// The backend the authenticator secret of `account` is kept in
+pub(crate) fn totp_backend(account: &Account) -> Backend {
+    Reference::backend(&account.totp_reference)
+}
+
+fn load_value(plain: &Option<String>, reference: &Option<Reference>) -> Result<Option<String>> {
+    match reference {
+        Some(Reference::Keyring(key)) => keyring::lookup(key),
+        Some(Reference::File(key)) => file::lookup(key),
+        None => Ok(plain.clone()),
+    }
+}
+
+/// Stores `value` in `backend` under a new key, returning what the config keeps in its place
+fn store_value(
+    backend: Backend,
+    label: &str,
+    value: &str,
+) -> Result<(Option<String>, Option<Reference>)> {
+    let key = new_key()?;
+
+    match backend {
+        Backend::Plain => Ok((Some(value.to_owned()), None)),
+        Backend::Keyring => {
+            keyring::store(&key, label, value)?;
+            Ok((None, Some(Reference::Keyring(key))))
+        }
+
+        Backend::File => {
+            file::store(&key, value)?;
+            Ok((None, Some(Reference::File(key))))
+        }
+    }
+}
+
+pub(crate) fn load(account: &Account) -> Result<String> {
+    load_value(&account.cookie, &account.secret)?.ok_or(Error::Config(format!(
+        "cookie of account: {} not found",
+        account.name
+    )))
+}
+
+/// Stores `cookie` in `backend` under a new key and points `account` at it, whatever the account
+/// pointed at before is left in place to be deleted once the config is stored
+pub(crate) fn store(account: &mut Account, backend: Backend, cookie: &str) -> Result<()> {
+    let label = format!("{} account: {}", env!("CARGO_BIN_NAME"), account.name);
+    (account.cookie, account.secret) = store_value(backend, &label, cookie)?;
+    Ok(())
+}
+
+/// The authenticator secret two step verification codes are generated from, if one was stored
+pub(crate) fn load_totp(account: &Account) -> Result<Option<String>> {
+    load_value(&account.totp_secret, &account.totp_reference)
+}
+
+/// Like `store`, for the authenticator secret
+pub(crate) fn store_totp(account: &mut Account, backend: Backend, secret: &str) -> Result<()> {
+    let label = format!("{} authenticator: {}", env!("CARGO_BIN_NAME"), account.name);
+
+    (account.totp_secret, account.totp_reference) = store_value(backend, &label, secret)?;
+    Ok(())
+}
+
+pub(crate) fn delete(reference: &Reference) -> Result<()> {
+    match reference {
+        Reference::Keyring(key) => keyring::delete(key),
+        Reference::File(key) => file::delete(key),
+    }
+}
+
+fn new_key() -> Result<String> {
+    let mut bytes = [0; 16];
+    openssl::rand::rand_bytes(&mut bytes)
+        .map_err(|error| Error::Io(format!("failed to generate a secret key: {error}")))?;
+
+    Ok(bytes.iter().map(|byte| format!("{byte:02x}")).collect())
+}
@@ -0,0 +1,80 @@
+use openssl::{hash::MessageDigest, pkey::PKey, sign::Signer};
+
+use crate::error::{Error, Result};
+
+/// Seconds a code is valid for, the same for every authenticator app
+const PERIOD: u64 = 30;
+const DIGITS: u32 = 6;
+
+fn decode_base32(secret: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let (mut buffer, mut bits) = (0_u32, 0);
+
+    // Secrets are often shown in groups, padded or in lowercase
+    for character in secret
+        .chars()
+        .filter(|character| !character.is_whitespace() && !matches!(character, '=' | '-'))
+    {
+        let value = match character.to_ascii_uppercase() {
+            character @ 'A'..='Z' => character as u32 - 'A' as u32,
+            character @ '2'..='7' => character as u32 - '2' as u32 + 26,
+            _ => return None,
+        };
+
+        buffer = (buffer << 5) | value;
+        bits += 5;
+
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+
+    Some(bytes).filter(|bytes| !bytes.is_empty())
+}
+
+/// The code an authenticator app shows for the base32 `secret` at `time`, in unix seconds
+pub(crate) fn code(secret: &str, time: u64) -> Result<String> {
+    let key = decode_base32(secret).ok_or(Error::Config(
+        "the authenticator secret isn't valid base32".to_string(),
+    ))?;
+
+    let hmac = PKey::hmac(&key)
+        .and_then(|key| {
+            let mut signer = Signer::new(MessageDigest::sha1(), &key)?;
+            signer.update(&(time / PERIOD).to_be_bytes())?;
+            signer.sign_to_vec()
+        })
+        .map_err(|error| {
+            Error::Io(format!(
+                "failed to generate the authenticator code: {error}"
+            ))
+        })?;
+
+    let offset = (hmac[hmac.len() - 1] & 0xf) as usize;
+    let value = u32::from_be_bytes([
+        hmac[offset] & 0x7f,
+        hmac[offset + 1],
+        hmac[offset + 2],
+        hmac[offset + 3],
+    ]);
+
+    Ok(format!("{:06}", value % 10_u32.pow(DIGITS)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::code;
+
+    #[test]
+    fn rfc_6238_codes() {
+        // The base32 form of the RFC's "12345678901234567890" secret
+        let secret = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+        assert_eq!(code(secret, 59).unwrap(), "287082");
+        assert_eq!(code(secret, 1111111109).unwrap(), "081804");
+        assert_eq!(code(&secret.to_lowercase(), 1234567890).unwrap(), "005924");
+        assert!(code("not base32!", 59).is_err());
+    }
+}
@@ -0,0 +1,252 @@
+use chrono::{DateTime, NaiveDateTime, TimeDelta, Utc};
+use reqwest::{
+    Method, Response,
+    header::{self, HeaderMap, HeaderValue},
+};
+use roblox_api::{ApiError, Error};
+use serde::{Deserialize, Serialize};
+
+use super::WebClient;
+
+pub(crate) const URL: &str = "https://auth.roblox.com/v2";
+const TICKET_URL: &str = "https://auth.roblox.com/v1/authentication-ticket";
+
+/// The response header the authentication ticket is handed out in
+const TICKET_HEADER: &str = "rbx-authentication-ticket";
+
+/// The cookie roblox hands out on login
+const COOKIE_NAME: &str = ".ROBLOSECURITY";
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LoginUser {
+    pub(crate) id: u64,
+    pub(crate) name: String,
+    pub(crate) display_name: String,
+}
+
+/// A `.ROBLOSECURITY` cookie roblox handed out
+#[derive(Clone, Debug)]
+pub(crate) struct SessionCookie {
+    pub(crate) value: String,
+    /// When the browser was told to drop the cookie
+    pub(crate) expires: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct LoginResponse {
+    user: LoginUser,
+}
+
+#[derive(Serialize)]
+struct LoginRequest<'a> {
+    ctype: &'a str,
+    cvalue: &'a str,
+    password: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RedeemRequest<'a> {
+    authentication_ticket: &'a str,
+}
+
+/// Finishes a quick login once its code was validated on another device, returning the user and
+/// the cookie of the new session
+pub(crate) async fn login_with_token(
+    client: &mut WebClient,
+    code: &str,
+    private_key: &str,
+) -> Result<(LoginUser, SessionCookie), Error> {
+    let request = LoginRequest {
+        ctype: "AuthToken",
+        cvalue: code,
+        password: private_key,
+    };
+
+    let response = client
+        .request(
+            Method::POST,
+            &format!("{URL}/login"),
+            &[],
+            Some(&request),
+            None,
+        )
+        .await?;
+
+    let cookie = session_cookie(&response)?;
+    let response: LoginResponse = response.json().await?;
+    Ok((response.user, cookie))
+}
+
+/// Invalidates the session of the cookie the client was made with
+pub(crate) async fn logout(client: &mut WebClient) -> Result<(), Error> {
+    client
+        .request(
+            Method::POST,
+            &format!("{URL}/logout"),
+            &[],
+            None::<&()>,
+            None,
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Invalidates every session of the user, including the current one, returning the cookie of the
+/// session that replaces it
+pub(crate) async fn logout_from_all_sessions(
+    client: &mut WebClient,
+) -> Result<SessionCookie, Error> {
+    let response = client
+        .request(
+            Method::POST,
+            &format!("{URL}/logoutfromallsessionsandreauthenticate"),
+            &[],
+            None::<&()>,
+            None,
+        )
+        .await?;
+
+    session_cookie(&response)
+}
+
+/// A one time ticket another client can trade for a new session of the user
+pub(crate) async fn authentication_ticket(client: &mut WebClient) -> Result<String, Error> {
+    let mut headers = HeaderMap::new();
+    // Tickets are only handed out to requests which look like they came from the website
+    headers.insert(
+        header::REFERER,
+        HeaderValue::from_static("https://www.roblox.com/"),
+    );
+
+    let response = client
+        .request(Method::POST, TICKET_URL, &[], None::<&()>, Some(headers))
+        .await?;
+
+    response
+        .headers()
+        .get(TICKET_HEADER)
+        .and_then(|ticket| ticket.to_str().ok())
+        .map(|ticket| ticket.to_owned())
+        .ok_or(Error::ApiError(ApiError::Unknown(
+            response.status().as_u16(),
+            Some("no authentication ticket was handed out".to_string()),
+        )))
+}
+
+/// Trades `ticket` for the cookie of a new session
+pub(crate) async fn redeem_authentication_ticket(
+    client: &mut WebClient,
+    ticket: &str,
+) -> Result<SessionCookie, Error> {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        "rbxauthenticationnegotiation",
+        HeaderValue::from_static("1"),
+    );
+
+    let request = RedeemRequest {
+        authentication_ticket: ticket,
+    };
+
+    let response = client
+        .request(
+            Method::POST,
+            &format!("{TICKET_URL}/redeem"),
+            &[],
+            Some(&request),
+            Some(headers),
+        )
+        .await?;
+
+    session_cookie(&response)
+}
+
+/// The session cookie set by `response`
+fn session_cookie(response: &Response) -> Result<SessionCookie, Error> {
+    response
+        .headers()
+        .get_all(header::SET_COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .find_map(parse_set_cookie)
+        .ok_or(Error::ApiError(ApiError::Unknown(
+            response.status().as_u16(),
+            Some("no cookie was handed out for the session".to_string()),
+        )))
+}
+
+/// The session cookie in a `Set-Cookie` header, if that's the cookie it sets
+fn parse_set_cookie(header: &str) -> Option<SessionCookie> {
+    let mut attributes = header.split(';').map(str::trim);
+    let (name, value) = attributes.next()?.split_once('=')?;
+    if name != COOKIE_NAME {
+        return None;
+    }
+
+    let mut expires = None;
+    for (key, attribute) in attributes.filter_map(|x| x.split_once('=')) {
+        match key.to_lowercase().as_str() {
+            // Max-Age wins over Expires when both are given
+            "max-age" => {
+                if let Ok(seconds) = attribute.parse() {
+                    return Some(SessionCookie {
+                        value: value.to_owned(),
+                        expires: Some(Utc::now() + TimeDelta::seconds(seconds)),
+                    });
+                }
+            }
+
+            "expires" => {
+                expires = DateTime::parse_from_rfc2822(attribute)
+                    .map(|x| x.to_utc())
+                    .or_else(|_| {
+                        NaiveDateTime::parse_from_str(attribute, "%a, %d-%b-%Y %H:%M:%S GMT")
+                            .map(|x| x.and_utc())
+                    })
+                    .ok();
+            }
+
+            _ => {}
+        }
+    }
+
+    Some(SessionCookie {
+        value: value.to_owned(),
+        expires,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::parse_set_cookie;
+
+    #[test]
+    fn set_cookie_expiry() {
+        let cookie = parse_set_cookie(
+            ".ROBLOSECURITY=_|WARNING:-DO-NOT-SHARE-THIS.|_abc; domain=.roblox.com; \
+             expires=Mon, 18-Oct-2027 12:30:00 GMT; path=/; secure; HttpOnly",
+        )
+        .unwrap();
+
+        assert_eq!(cookie.value, "_|WARNING:-DO-NOT-SHARE-THIS.|_abc");
+        assert_eq!(
+            cookie.expires,
+            Some(Utc.with_ymd_and_hms(2027, 10, 18, 12, 30, 0).unwrap())
+        );
+
+        let cookie =
+            parse_set_cookie(".ROBLOSECURITY=abc; expires=Mon, 18 Oct 2027 12:30:00 GMT; path=/")
+                .unwrap();
+        assert_eq!(
+            cookie.expires,
+            Some(Utc.with_ymd_and_hms(2027, 10, 18, 12, 30, 0).unwrap())
+        );
+
+        assert!(parse_set_cookie("RBXEventTrackerV2=abc; path=/").is_none());
+    }
+}
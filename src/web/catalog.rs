@@ -0,0 +1,50 @@
+use roblox_api::{AssetTypeId, Error, Paging};
+use serde::Deserialize;
+
+use super::{WebClient, paging_query, query_pairs};
+
+pub(crate) const URL: &str = "https://catalog.roblox.com/v2";
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FavoriteAsset {
+    pub(crate) id: u64,
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+
+    pub(crate) creator_target_id: Option<u64>,
+    pub(crate) creator_name: Option<String>,
+    pub(crate) creator_type: Option<String>,
+    pub(crate) creator_has_verified_badge: Option<bool>,
+
+    pub(crate) price: Option<u64>,
+    pub(crate) lowest_price: Option<u64>,
+    pub(crate) favorite_count: Option<u64>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct FavoriteAssets {
+    #[serde(rename = "data")]
+    pub(crate) assets: Vec<FavoriteAsset>,
+    #[serde(rename = "nextPageCursor")]
+    pub(crate) next_cursor: Option<String>,
+    #[serde(rename = "previousPageCursor")]
+    pub(crate) previous_cursor: Option<String>,
+}
+
+pub(crate) async fn user_favorites(
+    client: &mut WebClient,
+    id: u64,
+    asset_kind: AssetTypeId,
+    paging: Paging<'_>,
+) -> Result<FavoriteAssets, Error> {
+    let query = paging_query(paging, 10);
+    let kind = asset_kind as u8;
+
+    client
+        .get(
+            &format!("{URL}/favorites/users/{id}/favorites/{kind}/assets"),
+            &query_pairs(&query),
+        )
+        .await
+}
@@ -0,0 +1,31 @@
+use roblox_api::{
+    Error, Paging,
+    api::friends::v1::{Followers, URL},
+};
+
+use super::{WebClient, paging_query, query_pairs};
+
+pub(crate) async fn user_followers(
+    client: &mut WebClient,
+    id: u64,
+    paging: Paging<'_>,
+) -> Result<Followers, Error> {
+    let query = paging_query(paging, 10);
+    client
+        .get(&format!("{URL}/users/{id}/followers"), &query_pairs(&query))
+        .await
+}
+
+pub(crate) async fn user_followings(
+    client: &mut WebClient,
+    id: u64,
+    paging: Paging<'_>,
+) -> Result<Followers, Error> {
+    let query = paging_query(paging, 10);
+    client
+        .get(
+            &format!("{URL}/users/{id}/followings"),
+            &query_pairs(&query),
+        )
+        .await
+}
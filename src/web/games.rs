@@ -0,0 +1,46 @@
+use roblox_api::{Error, Paging, api::games::v1::URL};
+use serde::Deserialize;
+
+use super::{WebClient, paging_query, query_pairs};
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Server {
+    #[serde(rename = "id")]
+    pub(crate) job_id: String,
+    pub(crate) max_players: u16,
+    /// Missing while the server is starting up
+    #[serde(default)]
+    pub(crate) playing: u16,
+    pub(crate) fps: Option<f64>,
+    /// The average ping of the players in the server, in milliseconds
+    pub(crate) ping: Option<u32>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct Servers {
+    #[serde(rename = "data")]
+    pub(crate) servers: Vec<Server>,
+    #[serde(rename = "nextPageCursor")]
+    pub(crate) next_cursor: Option<String>,
+    #[serde(rename = "previousPageCursor")]
+    pub(crate) previous_cursor: Option<String>,
+}
+
+/// The public servers of the place `id`, the ones which are full are left out with `exclude_full`
+pub(crate) async fn public_servers(
+    client: &mut WebClient,
+    id: u64,
+    exclude_full: bool,
+    paging: Paging<'_>,
+) -> Result<Servers, Error> {
+    let mut query = paging_query(paging, 10);
+    query.push(("excludeFullGames", exclude_full.to_string()));
+
+    client
+        .get(
+            &format!("{URL}/games/{id}/servers/Public"),
+            &query_pairs(&query),
+        )
+        .await
+}
@@ -0,0 +1,34 @@
+use roblox_api::{DateTime, Error, Paging, api::groups::v1::URL};
+use serde::Deserialize;
+
+use super::{WebClient, paging_query, query_pairs};
+
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct NameHistoryItem {
+    pub(crate) name: String,
+    pub(crate) created: DateTime,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct NameHistory {
+    #[serde(rename = "data")]
+    pub(crate) names: Vec<NameHistoryItem>,
+    #[serde(rename = "nextPageCursor")]
+    pub(crate) next_cursor: Option<String>,
+    #[serde(rename = "previousPageCursor")]
+    pub(crate) previous_cursor: Option<String>,
+}
+
+pub(crate) async fn name_history(
+    client: &mut WebClient,
+    id: u64,
+    paging: Paging<'_>,
+) -> Result<NameHistory, Error> {
+    let query = paging_query(paging, 10);
+    client
+        .get(
+            &format!("{URL}/groups/{id}/name-history"),
+            &query_pairs(&query),
+        )
+        .await
+}
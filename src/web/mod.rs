@@ -0,0 +1,264 @@
+//! Bindings for the endpoints `roblox_api` doesn't cover, or doesn't expose the paging of
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use reqwest::{
+    Method, Response,
+    header::{self, HeaderMap, HeaderValue},
+};
+use roblox_api::{ApiError, Error, Paging, SortOrder, challenge::Challenge};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::json;
+
+pub(crate) mod auth;
+pub(crate) mod catalog;
+pub(crate) mod friends;
+pub(crate) mod games;
+pub(crate) mod groups;
+pub(crate) mod moderation;
+pub(crate) mod sessions;
+pub(crate) mod share_links;
+pub(crate) mod trades;
+pub(crate) mod two_step;
+
+const TOKEN_HEADER: &str = "x-csrf-token";
+
+/// The headers a challenge is handed out in, and sent back in once it was completed
+const CHALLENGE_ID_HEADER: &str = "rblx-challenge-id";
+const CHALLENGE_TYPE_HEADER: &str = "rblx-challenge-type";
+const CHALLENGE_METADATA_HEADER: &str = "rblx-challenge-metadata";
+
+const CHALLENGE_URL: &str = "https://apis.roblox.com/challenge/v1";
+
+#[derive(Debug, Deserialize)]
+struct ErrorJson {
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorsJson {
+    errors: Vec<ErrorJson>,
+}
+
+/// A cookie authenticated client which keeps track of the CSRF token roblox hands out
+#[derive(Debug)]
+pub(crate) struct WebClient {
+    client: reqwest::Client,
+    headers: HeaderMap,
+    /// Headers of a completed challenge, sent along with the next request only
+    challenge: HeaderMap,
+    /// Whether the last request was answered with a challenge
+    challenged: bool,
+}
+
+impl WebClient {
+    pub(crate) fn from_cookie(cookie: &str) -> Self {
+        let mut client = Self::anonymous();
+        if let Ok(cookie) = HeaderValue::from_str(&format!(".ROBLOSECURITY={cookie}")) {
+            client.headers.append(header::COOKIE, cookie);
+        }
+
+        client
+    }
+
+    /// A client without any account, for logging in
+    pub(crate) fn anonymous() -> Self {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::USER_AGENT,
+            HeaderValue::from_static("Roblox/WinInet"),
+        );
+
+        headers.insert(
+            header::COOKIE,
+            HeaderValue::from_static("RBXEventTrackerV2=&browserid=2"),
+        );
+
+        Self {
+            client: reqwest::Client::new(),
+            headers,
+            challenge: HeaderMap::new(),
+            challenged: false,
+        }
+    }
+
+    /// Whether the last request was answered with a challenge, so it's this client that has to
+    /// send the completed one back
+    pub(crate) fn challenged(&self) -> bool {
+        self.challenged
+    }
+
+    /// Continues `challenge` with the token it was completed with, and sends the completed
+    /// challenge along with the next request, like `Client::queue_challenge`
+    pub(crate) async fn queue_challenge(
+        &mut self,
+        challenge: &Challenge,
+        verification_token: &str,
+    ) -> Result<(), Error> {
+        let kind = serde_json::to_value(challenge.kind)
+            .ok()
+            .and_then(|kind| kind.as_str().map(str::to_owned))
+            .unwrap_or_default();
+
+        let metadata = serde_json::to_value(&challenge.metadata).map_err(|_| Error::BadJson)?;
+        let metadata = json!({
+            "verificationToken": verification_token,
+            "challengeId": metadata["challengeId"],
+            "actionType": metadata["actionType"],
+            "rememberDevice": metadata["rememberDevice"],
+        })
+        .to_string();
+
+        // The challenge has to be continued before the request is sent again, or it fails
+        let request = json!({
+            "challengeId": challenge.id,
+            "challengeType": kind,
+            "challengeMetadata": metadata,
+        });
+
+        self.request(
+            Method::POST,
+            &format!("{CHALLENGE_URL}/continue"),
+            &[],
+            Some(&request),
+            None,
+        )
+        .await?;
+
+        let value = |value: &str| {
+            HeaderValue::from_str(value).map_err(|_| Error::ApiError(ApiError::InvalidChallengeId))
+        };
+
+        self.challenge
+            .insert(CHALLENGE_ID_HEADER, value(&challenge.id)?);
+        self.challenge.insert(CHALLENGE_TYPE_HEADER, value(&kind)?);
+        self.challenge.insert(
+            CHALLENGE_METADATA_HEADER,
+            value(&STANDARD.encode(metadata))?,
+        );
+
+        Ok(())
+    }
+
+    pub(crate) async fn get<T: DeserializeOwned>(
+        &mut self,
+        url: &str,
+        query: &[(&str, &str)],
+    ) -> Result<T, Error> {
+        let response = self
+            .request(Method::GET, url, query, None::<&()>, None)
+            .await?;
+        Ok(response.json().await?)
+    }
+
+    /// Sends a request, retrying once with a fresh CSRF token if roblox rejected the old one
+    pub(crate) async fn request<R: Serialize>(
+        &mut self,
+        method: Method,
+        url: &str,
+        query: &[(&str, &str)],
+        body: Option<&R>,
+        headers: Option<HeaderMap>,
+    ) -> Result<Response, Error> {
+        let mut retried = false;
+        let challenge = std::mem::take(&mut self.challenge);
+        self.challenged = false;
+
+        loop {
+            let mut builder = self
+                .client
+                .request(method.clone(), url)
+                .headers(self.headers.clone())
+                .headers(challenge.clone())
+                .query(query);
+
+            if let Some(headers) = &headers {
+                builder = builder.headers(headers.clone());
+            }
+
+            if let Some(body) = body {
+                builder = builder.json(body);
+            }
+
+            let response = builder.send().await?;
+
+            if let Some(token) = response.headers().get(TOKEN_HEADER) {
+                let refreshed = self.headers.get(TOKEN_HEADER) != Some(token);
+                self.headers.insert(TOKEN_HEADER, token.clone());
+
+                if response.status() == 403 && refreshed && !retried {
+                    retried = true;
+                    continue;
+                }
+            }
+
+            if response.status().is_success() {
+                return Ok(response);
+            }
+
+            if let Some(challenge) = challenge_from_response(&response) {
+                self.challenged = true;
+                return Err(Error::ApiError(ApiError::ChallengeRequired(challenge)));
+            }
+
+            return Err(error_from_response(response).await);
+        }
+    }
+}
+
+/// The challenge roblox wants completed before it answers the request, if it asked for one
+fn challenge_from_response(response: &Response) -> Option<Challenge> {
+    let header = |name| response.headers().get(name)?.to_str().ok();
+
+    let metadata = STANDARD.decode(header(CHALLENGE_METADATA_HEADER)?).ok()?;
+    let metadata: serde_json::Value = serde_json::from_slice(&metadata).ok()?;
+
+    // Built through serde, since `roblox_api` keeps some of the fields to itself
+    serde_json::from_value(json!({
+        "id": header(CHALLENGE_ID_HEADER)?,
+        "kind": header(CHALLENGE_TYPE_HEADER)?,
+        "metadata": metadata,
+    }))
+    .ok()
+}
+
+async fn error_from_response(response: Response) -> Error {
+    let code = response.status().as_u16();
+    let bytes = response.bytes().await.unwrap_or_default();
+
+    let message = match serde_json::from_slice::<ErrorsJson>(&bytes) {
+        Ok(errors) => errors.errors.into_iter().next().map(|error| error.message),
+        Err(_) => match serde_json::from_slice::<ErrorJson>(&bytes) {
+            Ok(error) => Some(error.message),
+            Err(_) => Some(String::from_utf8_lossy(&bytes).to_string()).filter(|x| !x.is_empty()),
+        },
+    };
+
+    Error::ApiError(match code {
+        401 => ApiError::Unauthorized,
+        429 => ApiError::Ratelimited,
+        500 => ApiError::Internal,
+        _ => ApiError::Unknown(code, message),
+    })
+}
+
+/// Query pairs of `paging`, in the form the v1 apis expect them
+fn paging_query(paging: Paging<'_>, default_limit: u16) -> Vec<(&'static str, String)> {
+    let order = match paging.order.unwrap_or_default() {
+        SortOrder::Ascending => "Asc",
+        SortOrder::Descending => "Desc",
+    };
+
+    vec![
+        ("limit", paging.limit.unwrap_or(default_limit).to_string()),
+        ("sortOrder", order.to_string()),
+        ("cursor", paging.cursor.unwrap_or_default().to_string()),
+    ]
+}
+
+/// Borrows owned query pairs in the form `WebClient` takes them
+fn query_pairs<'a>(query: &'a [(&'static str, String)]) -> Vec<(&'static str, &'a str)> {
+    query
+        .iter()
+        .map(|(key, value)| (*key, value.as_str()))
+        .collect()
+}
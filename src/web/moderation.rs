@@ -0,0 +1,28 @@
+use roblox_api::Error;
+use serde::Deserialize;
+
+use super::WebClient;
+
+pub(crate) const URL: &str = "https://usermoderation.roblox.com/v1";
+
+/// The punishment keeping an account from being used, roblox answers with an empty object when
+/// there is none
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Punishment {
+    /// Like `Ban 1 Day` or `Delete`
+    pub(crate) punishment_type_description: Option<String>,
+    pub(crate) end_date: Option<String>,
+}
+
+/// The punishment of the authenticated user, if there is one
+pub(crate) async fn not_approved(client: &mut WebClient) -> Result<Option<Punishment>, Error> {
+    let punishment: Punishment = client.get(&format!("{URL}/not-approved"), &[]).await?;
+
+    Ok(Some(punishment).filter(|punishment| {
+        punishment
+            .punishment_type_description
+            .as_ref()
+            .is_some_and(|description| !description.is_empty())
+    }))
+}
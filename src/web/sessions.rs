@@ -0,0 +1,58 @@
+use roblox_api::Error;
+use serde::Deserialize;
+
+use super::WebClient;
+
+pub(crate) const URL: &str = "https://apis.roblox.com/token-metadata-service/v1";
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub(crate) struct SessionAgent {
+    pub(crate) value: Option<String>,
+    pub(crate) os: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub(crate) struct SessionLocation {
+    pub(crate) city: Option<String>,
+    pub(crate) subdivision: Option<String>,
+    pub(crate) country: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Session {
+    #[serde(default)]
+    pub(crate) agent: SessionAgent,
+    #[serde(default)]
+    pub(crate) location: SessionLocation,
+    pub(crate) last_accessed_ip: Option<String>,
+    /// Milliseconds since the unix epoch, sent as a string
+    #[serde(rename = "lastAccessedTimestampEpochMilliseconds")]
+    pub(crate) last_accessed: Option<serde_json::Value>,
+    #[serde(default)]
+    pub(crate) is_current_session: bool,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Sessions {
+    pub(crate) sessions: Vec<Session>,
+    pub(crate) next_cursor: Option<String>,
+    #[serde(default)]
+    pub(crate) has_more: bool,
+}
+
+pub(crate) async fn sessions(
+    client: &mut WebClient,
+    cursor: Option<&str>,
+) -> Result<Sessions, Error> {
+    client
+        .get(
+            &format!("{URL}/sessions"),
+            &[
+                ("nextCursor", cursor.unwrap_or_default()),
+                ("desiredLimit", "25"),
+            ],
+        )
+        .await
+}
@@ -0,0 +1,53 @@
+use reqwest::Method;
+use roblox_api::Error;
+use serde::{Deserialize, Serialize};
+
+use super::WebClient;
+
+pub(crate) const URL: &str = "https://apis.roblox.com/sharelinks/v1";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ResolveRequest<'a> {
+    link_id: &'a str,
+    link_type: &'a str,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PrivateServerInvite {
+    /// `Valid`, or why the link can't be used, like `Expired`
+    pub(crate) status: String,
+    pub(crate) place_id: Option<u64>,
+    pub(crate) link_code: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ResolvedLink {
+    pub(crate) private_server_invite_data: Option<PrivateServerInvite>,
+}
+
+/// What the share link `code` of `kind`, the `type` of the link, points at
+pub(crate) async fn resolve(
+    client: &mut WebClient,
+    code: &str,
+    kind: &str,
+) -> Result<ResolvedLink, Error> {
+    let request = ResolveRequest {
+        link_id: code,
+        link_type: kind,
+    };
+
+    let response = client
+        .request(
+            Method::POST,
+            &format!("{URL}/resolve-link"),
+            &[],
+            Some(&request),
+            None,
+        )
+        .await?;
+
+    Ok(response.json().await?)
+}
@@ -0,0 +1,98 @@
+use std::fmt;
+
+use roblox_api::{DateTime, Error, Paging};
+use serde::Deserialize;
+
+use super::{WebClient, paging_query, query_pairs};
+
+pub(crate) const URL: &str = "https://trades.roblox.com/v1";
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum TradeStatusType {
+    Inbound,
+    Outbound,
+    Completed,
+    Inactive,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TradeUser {
+    pub(crate) id: u64,
+    pub(crate) name: String,
+    pub(crate) display_name: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Trade {
+    pub(crate) id: u64,
+    pub(crate) user: TradeUser,
+    pub(crate) created: DateTime,
+    pub(crate) expiration: Option<DateTime>,
+    pub(crate) is_active: bool,
+    pub(crate) status: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct Trades {
+    #[serde(rename = "data")]
+    pub(crate) trades: Vec<Trade>,
+    #[serde(rename = "nextPageCursor")]
+    pub(crate) next_cursor: Option<String>,
+    #[serde(rename = "previousPageCursor")]
+    pub(crate) previous_cursor: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TradeAsset {
+    #[serde(rename = "id")]
+    pub(crate) user_asset_id: u64,
+    pub(crate) asset_id: u64,
+    pub(crate) name: String,
+    pub(crate) serial_number: Option<u64>,
+    pub(crate) recent_average_price: Option<u64>,
+    pub(crate) original_price: Option<u64>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TradeOffer {
+    pub(crate) user: TradeUser,
+    pub(crate) user_assets: Vec<TradeAsset>,
+    pub(crate) robux: u64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct TradeDetails {
+    pub(crate) offers: Vec<TradeOffer>,
+}
+
+impl fmt::Display for TradeStatusType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Inbound => "Inbound",
+            Self::Outbound => "Outbound",
+            Self::Completed => "Completed",
+            Self::Inactive => "Inactive",
+        };
+
+        write!(f, "{s}")
+    }
+}
+
+pub(crate) async fn trades(
+    client: &mut WebClient,
+    kind: TradeStatusType,
+    paging: Paging<'_>,
+) -> Result<Trades, Error> {
+    let query = paging_query(paging, 10);
+    client
+        .get(&format!("{URL}/trades/{kind}"), &query_pairs(&query))
+        .await
+}
+
+pub(crate) async fn trade_details(client: &mut WebClient, id: u64) -> Result<TradeDetails, Error> {
+    client.get(&format!("{URL}/trades/{id}"), &[]).await
+}
@@ -0,0 +1,94 @@
+use reqwest::Method;
+use roblox_api::Error;
+use serde::{Deserialize, Serialize};
+
+use super::WebClient;
+
+pub(crate) const URL: &str = "https://twostepverification.roblox.com/v1";
+
+/// Where the code answering a challenge comes from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Channel {
+    Authenticator,
+    Email,
+}
+
+impl Channel {
+    fn path(self) -> &'static str {
+        match self {
+            Self::Authenticator => "authenticator",
+            Self::Email => "email",
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ChallengeRequest<'a> {
+    challenge_id: &'a str,
+    action_type: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<&'a str>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VerifyResponse {
+    verification_token: String,
+}
+
+/// Mails a code for the challenge to the user
+pub(crate) async fn send_email_code(
+    client: &mut WebClient,
+    user_id: u64,
+    challenge_id: &str,
+    action_type: &str,
+) -> Result<(), Error> {
+    let request = ChallengeRequest {
+        challenge_id,
+        action_type,
+        code: None,
+    };
+
+    client
+        .request(
+            Method::POST,
+            &format!("{URL}/users/{user_id}/challenges/email/send-code"),
+            &[],
+            Some(&request),
+            None,
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Trades the code for the verification token the challenge is continued with
+pub(crate) async fn verify(
+    client: &mut WebClient,
+    user_id: u64,
+    channel: Channel,
+    challenge_id: &str,
+    action_type: &str,
+    code: &str,
+) -> Result<String, Error> {
+    let request = ChallengeRequest {
+        challenge_id,
+        action_type,
+        code: Some(code),
+    };
+
+    let response: VerifyResponse = client
+        .request(
+            Method::POST,
+            &format!("{URL}/users/{user_id}/challenges/{}/verify", channel.path()),
+            &[],
+            Some(&request),
+            None,
+        )
+        .await?
+        .json()
+        .await?;
+
+    Ok(response.verification_token)
+}